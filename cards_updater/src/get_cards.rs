@@ -1,35 +1,39 @@
-use std::{
-    fs::File,
-    io::BufWriter,
-    path::{Path, PathBuf},
-    str::FromStr,
-};
+use std::{fs::File, io::BufWriter, path::Path, str::FromStr};
 
 use crate::ErrorKind;
-use data::cards::{Card, CardClass, GameExtension};
+use data::cards::{
+    parse_traits, Card, CardCategory, CardClass, CardType, GameExtension, Rarity, SetType,
+};
+use data::{covers, extension_mapping::ExtensionMapping};
 use scraper::selectable::Selectable;
 
 use crate::get_number_of_cards::get_number_of_cards;
 
 const CARDS_PER_PAGE: u32 = 15;
-const PAGE_API_URL: &str = "https://en.shadowverse-evolve.com/cards/searchresults_ex?card_name=&class%5B0%5D=all&title=&expansion_name=&cost%5B0%5D=all&card_kind%5B0%5D=all&rare%5B0%5D=all&power_from=&power_to=&hp_from=&hp_to=&type=&ability=&keyword=&view=text&t=1711058152734&_=1711057240616&sort=no";
-const DETAIL_PAGE_URL: &str = "https://en.shadowverse-evolve.com/cards/?cardno=";
+const PAGE_API_PATH: &str = "/cards/searchresults_ex?card_name=&class%5B0%5D=all&title=&expansion_name=&cost%5B0%5D=all&card_kind%5B0%5D=all&rare%5B0%5D=all&power_from=&power_to=&hp_from=&hp_to=&type=&ability=&keyword=&view=text&t=1711058152734&_=1711057240616&sort=no";
+const DETAIL_PAGE_PATH: &str = "/cards/?cardno=";
+const COVERS_PATH: &str = "/wordpress/wp-content/images/cardlist";
 
-pub async fn get_max_page() -> u32 {
-    let number_of_cards = get_number_of_cards().await.unwrap();
+pub async fn get_max_page(source_url: &str) -> u32 {
+    let number_of_cards = get_number_of_cards(source_url).await.unwrap();
     number_of_cards.div_ceil(CARDS_PER_PAGE)
 }
 
-pub async fn get_cards(page_index: u32) -> Result<Vec<String>, ErrorKind> {
+pub async fn get_cards(source_url: &str, page_index: u32) -> Result<Vec<String>, ErrorKind> {
     let mut cards_number = Vec::new();
 
-    let response = ureq::get(&format!("{}&page={}", PAGE_API_URL, page_index))
-        .call()
-        .unwrap()
-        .into_string()
-        .map_err(|_| ErrorKind::GetMetadatasError {
-            page_number: page_index,
-        })?;
+    let response = ureq::get(&format!(
+        "{}{}&page={}",
+        source_url.trim_end_matches('/'),
+        PAGE_API_PATH,
+        page_index
+    ))
+    .call()
+    .unwrap()
+    .into_string()
+    .map_err(|_| ErrorKind::GetMetadatasError {
+        page_number: page_index,
+    })?;
     let html = scraper::Html::parse_document(&response);
     let cards_selector = scraper::Selector::parse("li").unwrap();
     let html_cards = html.select(&cards_selector);
@@ -48,15 +52,24 @@ pub async fn get_cards(page_index: u32) -> Result<Vec<String>, ErrorKind> {
     Ok(cards_number)
 }
 
-pub fn download_card(card_number: &str, covers_directory: &PathBuf) -> Result<Card, ErrorKind> {
+pub fn download_card(
+    source_url: &str,
+    card_number: &str,
+    extension_mappings: &[ExtensionMapping],
+) -> Result<Card, ErrorKind> {
     // Extract the data from the card detail page
-    let response = ureq::get(&format!("{}{}", DETAIL_PAGE_URL, card_number))
-        .call()
-        .unwrap()
-        .into_string()
-        .map_err(|_| ErrorKind::DownloadCardError {
-            card_number: card_number.to_string(),
-        })?;
+    let response = ureq::get(&format!(
+        "{}{}{}",
+        source_url.trim_end_matches('/'),
+        DETAIL_PAGE_PATH,
+        card_number
+    ))
+    .call()
+    .unwrap()
+    .into_string()
+    .map_err(|_| ErrorKind::DownloadCardError {
+        card_number: card_number.to_string(),
+    })?;
     let html_card = scraper::Html::parse_document(&response);
 
     let name = html_card
@@ -94,7 +107,9 @@ pub fn download_card(card_number: &str, covers_directory: &PathBuf) -> Result<Ca
         }
     })?;
 
-    let is_evolved = card_type.contains("Evolved");
+    let card_type = CardType::from(card_type);
+    let is_evolved = card_type.is_evolved();
+    let category = CardCategory::detect(&card_type, &card_number);
 
     let card_trait =
         get_from_block_with_text("Trait", &infos).map_err(|_| ErrorKind::DownloadCardError {
@@ -134,25 +149,28 @@ pub fn download_card(card_number: &str, covers_directory: &PathBuf) -> Result<Ca
         .unwrap_or_default()
         .to_string();
 
-    // get_image(&card_number, covers_directory);
-
-    let extension_id = get_extension_id(&card_number);
+    let extension_id = data::extension_mapping::extension_id(&card_number, extension_mappings);
 
     Ok(Card {
         id: card_number.to_string().clone(),
         name,
         card_class: CardClass::from(card_class),
         card_type,
-        card_trait,
-        rarity: card_rarity,
+        traits: parse_traits(&card_trait),
+        rarity: Rarity::from(card_rarity),
         hp: extract_number_from_str(&defense).parse::<u8>().unwrap_or(0),
         attack: extract_number_from_str(&power).parse::<u8>().unwrap_or(0),
         cost: extract_number_from_str(&cost).parse::<u8>().unwrap_or(0),
         is_evolved,
+        category,
+        // The forms are linked once every card is downloaded
+        evolution: None,
         details,
         extension: GameExtension {
             id: extension_id.to_string(),
             name: card_extension,
+            set_type: SetType::from_extension_id(extension_id),
+            ..Default::default()
         },
     })
 }
@@ -183,30 +201,44 @@ fn get_from_block_with_text(
     Ok("Unknown".to_string())
 }
 
-fn get_extension_id(card_number: &str) -> &str {
-    card_number.split('-').next().unwrap()
-}
+/// Download the cover of a card in the covers directory and create its thumbnail, the cover is
+/// only downloaded once
+pub fn download_cover(
+    source_url: &str,
+    card_number: &str,
+    extension_id: &str,
+    covers_directory: &Path,
+) -> Result<(), ErrorKind> {
+    let cover_error = || ErrorKind::DownloadCoverError {
+        card_number: card_number.to_string(),
+    };
 
-fn get_image(card_number: &str, output_directory: &Path) {
-    let mut extension_id = get_extension_id(card_number);
-    if extension_id.contains("BSF") {
-        extension_id = "PR";
+    let cover_path = covers::cover_path(covers_directory, card_number);
+    if !cover_path.exists() {
+        let image_url = format!(
+            "{}{}/{}/{}.png",
+            source_url.trim_end_matches('/'),
+            COVERS_PATH,
+            extension_id,
+            card_number
+        );
+
+        let mut response = ureq::get(&image_url)
+            .set("user-agent", "shadowverse-utils/0.1")
+            .call()
+            .map_err(|_| cover_error())?
+            .into_reader();
+
+        let mut out_file = File::create(&cover_path).map_err(|_| cover_error())?;
+        std::io::copy(&mut response, &mut BufWriter::new(&mut out_file)).map_err(|_| {
+            let _ = std::fs::remove_file(&cover_path);
+            cover_error()
+        })?;
     }
 
-    let image_url = format!(
-        "https://en.shadowverse-evolve.com/wordpress/wp-content/images/cardlist/{}/{}.png",
-        extension_id, card_number
-    );
-
-    let mut response = ureq::get(&image_url)
-        .set("user-agent", "shadowverse-utils/0.1")
-        .call()
-        .unwrap()
-        .into_reader();
-
-    let mut out_file = File::create(output_directory.join(format!("{}.png", card_number))).unwrap();
+    covers::generate_thumbnail(covers_directory, card_number).map_err(|_| cover_error())?;
 
-    let _ = std::io::copy(&mut response, &mut BufWriter::new(&mut out_file));
+    Ok(())
 }
 
 fn extract_number_from_str(value: &str) -> &str {
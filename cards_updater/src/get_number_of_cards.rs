@@ -1,13 +1,17 @@
 use crate::ErrorKind;
 
-const ALL_SHADOWVERSE_CARDS_URL: &str = "https://en.shadowverse-evolve.com/cards/searchresults/?card_name=&class%5B%5D=all&title=&expansion_name=&cost%5B%5D=all&card_kind%5B%5D=all&rare%5B%5D=all&power_from=&power_to=&hp_from=&hp_to=&type=&ability=&keyword=";
+const ALL_SHADOWVERSE_CARDS_PATH: &str = "/cards/searchresults/?card_name=&class%5B%5D=all&title=&expansion_name=&cost%5B%5D=all&card_kind%5B%5D=all&rare%5B%5D=all&power_from=&power_to=&hp_from=&hp_to=&type=&ability=&keyword=";
 
-pub async fn get_number_of_cards() -> Result<u32, ErrorKind> {
-    let response = ureq::get(ALL_SHADOWVERSE_CARDS_URL)
-        .call()
-        .unwrap()
-        .into_string()
-        .map_err(|_| ErrorKind::NumberOfCardsError)?;
+pub async fn get_number_of_cards(source_url: &str) -> Result<u32, ErrorKind> {
+    let response = ureq::get(&format!(
+        "{}{}",
+        source_url.trim_end_matches('/'),
+        ALL_SHADOWVERSE_CARDS_PATH
+    ))
+    .call()
+    .unwrap()
+    .into_string()
+    .map_err(|_| ErrorKind::NumberOfCardsError)?;
 
     let html = scraper::Html::parse_document(&response);
     let number: u32 = html
@@ -9,8 +9,10 @@ pub enum ErrorKind {
     GetMetadatasError { page_number: u32 },
     #[error("Could not fetch the informations of the card {card_number}")]
     DownloadCardError { card_number: String },
+    #[error("Could not download the cover of the card {card_number}")]
+    DownloadCoverError { card_number: String },
 }
 
-pub use get_cards::{download_card, get_cards, get_max_page};
+pub use get_cards::{download_card, download_cover, get_cards, get_max_page};
 pub use get_number_of_cards::get_number_of_cards;
 use thiserror::Error;
@@ -4,19 +4,24 @@ use rusqlite::{
     types::{FromSql, ValueRef},
     ToSql,
 };
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct Card {
     pub id: String,
     pub name: String,
     pub card_class: CardClass,
-    pub card_type: String,
-    pub rarity: String,
-    pub card_trait: String,
+    pub card_type: CardType,
+    pub rarity: Rarity,
+    pub traits: Vec<String>,
     pub hp: u8,
     pub cost: u8,
     pub attack: u8,
     pub is_evolved: bool,
+    pub category: CardCategory,
+    /// Number of the other form of the card: the evolved form of a follower, or the base
+    /// follower of an evolved card
+    pub evolution: Option<String>,
     pub details: String,
     pub extension: GameExtension,
 }
@@ -24,20 +29,113 @@ pub struct Card {
 impl Card {
     pub fn name(&self) -> String {
         if self.is_evolved {
-            return format!("{} - Evolved", self.name);
+            format!("{} - Evolved", self.name)
         } else {
-            return self.name.clone();
+            self.name.clone()
         }
     }
+
+    /// The alternate printings of a card (SL, UR, SP, ...) have their own number but are played
+    /// as the same card, they share the same name and form
+    pub fn play_identity(&self) -> (String, bool) {
+        (self.name.to_lowercase(), self.is_evolved)
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct GameExtension {
     pub id: String,
     pub name: String,
+    pub set_type: SetType,
+    /// Date (`YYYY-MM-DD`) at which the extension was released, unknown when it is not listed in
+    /// the release dates of the configuration
+    pub release_date: Option<String>,
+    /// Number of collectible cards of the extension
+    pub card_count: u32,
+}
+
+/// Kind of product the cards of an extension are sold in
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum SetType {
+    Booster,
+    Starter,
+    Premium,
+    Promo,
+    #[default]
+    Other,
+}
+
+impl SetType {
+    pub const ALL: [SetType; 5] = [
+        SetType::Booster,
+        SetType::Starter,
+        SetType::Premium,
+        SetType::Promo,
+        SetType::Other,
+    ];
+
+    /// Recognize the set type from the prefix of the extension id, like `BP` in `BP01`
+    pub fn from_extension_id(extension_id: &str) -> Self {
+        let prefix: String = extension_id
+            .chars()
+            .take_while(|character| character.is_ascii_alphabetic())
+            .collect::<String>()
+            .to_uppercase();
+
+        match prefix.as_str() {
+            "BP" => SetType::Booster,
+            "SD" => SetType::Starter,
+            "CP" | "SP" => SetType::Premium,
+            "PR" => SetType::Promo,
+            _ => SetType::Other,
+        }
+    }
+}
+
+impl From<String> for SetType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Booster" => SetType::Booster,
+            "Starter" => SetType::Starter,
+            "Premium" => SetType::Premium,
+            "Promo" => SetType::Promo,
+            _ => SetType::Other,
+        }
+    }
+}
+
+impl FromSql for SetType {
+    fn column_result(value: ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        Option::<String>::column_result(value)
+            .map(|set_type| SetType::from(set_type.unwrap_or_default()))
+    }
+}
+
+impl ToSql for SetType {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl Display for SetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SetType::Booster => "Booster",
+                SetType::Starter => "Starter",
+                SetType::Premium => "Premium",
+                SetType::Promo => "Promo",
+                SetType::Other => "Other",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CardClass {
     Forestcraft,
     Swordcraft,
@@ -117,3 +215,347 @@ impl Display for CardClass {
         )
     }
 }
+
+/// Split the trait field displayed on the website into each of the traits of the card
+pub fn parse_traits(card_trait: &str) -> Vec<String> {
+    card_trait
+        .split('/')
+        .map(|card_trait| card_trait.trim().to_string())
+        .filter(|card_trait| !card_trait.is_empty() && card_trait != "-")
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CardType {
+    Follower,
+    EvolvedFollower,
+    Spell,
+    Amulet,
+    Leader,
+    Token,
+    /// Type not known by the application, kept as displayed on the website
+    Other(String),
+}
+
+impl CardType {
+    pub fn is_evolved(&self) -> bool {
+        self == &CardType::EvolvedFollower
+    }
+}
+
+impl From<String> for CardType {
+    fn from(value: String) -> Self {
+        let card_type = value.trim().to_lowercase();
+
+        // The website combines the kinds, like "Follower / Evolved" or "Spell / Token"
+        if card_type.contains("leader") {
+            CardType::Leader
+        } else if card_type.contains("token") {
+            CardType::Token
+        } else if card_type.contains("evolved") {
+            CardType::EvolvedFollower
+        } else if card_type.contains("follower") {
+            CardType::Follower
+        } else if card_type.contains("spell") {
+            CardType::Spell
+        } else if card_type.contains("amulet") {
+            CardType::Amulet
+        } else {
+            CardType::Other(value.trim().to_string())
+        }
+    }
+}
+
+impl From<CardType> for String {
+    fn from(value: CardType) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromSql for CardType {
+    fn column_result(value: ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value).map(CardType::from)
+    }
+}
+
+impl ToSql for CardType {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
+
+impl Display for CardType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CardType::Follower => "Follower",
+                CardType::EvolvedFollower => "Evolved Follower",
+                CardType::Spell => "Spell",
+                CardType::Amulet => "Amulet",
+                CardType::Leader => "Leader",
+                CardType::Token => "Token",
+                CardType::Other(card_type) => card_type,
+            }
+        )
+    }
+}
+
+/// Tokens and leaders are printed in the extensions but are not collected like the other cards
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum CardCategory {
+    #[default]
+    Collectible,
+    Token,
+    Leader,
+}
+
+impl CardCategory {
+    /// Recognize the category from the card type and from the number of the card, the tokens
+    /// are numbered with a `T` followed by digits like `BP01-T01` or `BP01-T01EN` and the leaders
+    /// like `BP01-LD01`
+    pub fn detect(card_type: &CardType, card_number: &str) -> Self {
+        let number = card_number
+            .split('-')
+            .nth(1)
+            .unwrap_or_default()
+            .to_uppercase();
+
+        if card_type == &CardType::Leader || number.starts_with("LD") {
+            CardCategory::Leader
+        } else if card_type == &CardType::Token
+            || number
+                .strip_prefix('T')
+                .is_some_and(|index| index.starts_with(|c: char| c.is_ascii_digit()))
+        {
+            CardCategory::Token
+        } else {
+            CardCategory::Collectible
+        }
+    }
+
+    pub fn is_collectible(&self) -> bool {
+        self == &CardCategory::Collectible
+    }
+}
+
+impl From<String> for CardCategory {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Token" => CardCategory::Token,
+            "Leader" => CardCategory::Leader,
+            _ => CardCategory::Collectible,
+        }
+    }
+}
+
+impl FromSql for CardCategory {
+    fn column_result(value: ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value).map(CardCategory::from)
+    }
+}
+
+impl ToSql for CardCategory {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
+
+impl Display for CardCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                CardCategory::Collectible => "Collectible",
+                CardCategory::Token => "Token",
+                CardCategory::Leader => "Leader",
+            }
+        )
+    }
+}
+
+/// Rarity of a card, ordered from the most common to the rarest printing
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Rarity {
+    Bronze,
+    Silver,
+    Gold,
+    Legendary,
+    Promo,
+    Premium,
+    Special,
+    SuperLegendary,
+    UltimateRare,
+    /// Rarity not known by the application, kept as displayed on the website
+    Other(String),
+}
+
+impl Rarity {
+    /// Alternate printings of a card that is also printed with a regular rarity
+    pub fn is_variant(&self) -> bool {
+        matches!(
+            self,
+            Rarity::Premium | Rarity::Special | Rarity::SuperLegendary | Rarity::UltimateRare
+        )
+    }
+}
+
+impl From<String> for Rarity {
+    fn from(value: String) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "bronze" | "br" => Rarity::Bronze,
+            "silver" | "sr" => Rarity::Silver,
+            "gold" | "gr" => Rarity::Gold,
+            "legendary" | "legend" | "lg" => Rarity::Legendary,
+            "promo" | "pr" => Rarity::Promo,
+            "premium" | "p" => Rarity::Premium,
+            "special" | "sp" => Rarity::Special,
+            "super legendary" | "super legend" | "sl" => Rarity::SuperLegendary,
+            "ultimate rare" | "ultimate" | "ur" => Rarity::UltimateRare,
+            _ => Rarity::Other(value.trim().to_string()),
+        }
+    }
+}
+
+impl From<Rarity> for String {
+    fn from(value: Rarity) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromSql for Rarity {
+    fn column_result(value: ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value).map(Rarity::from)
+    }
+}
+
+impl ToSql for Rarity {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
+
+impl Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Rarity::Bronze => "Bronze",
+                Rarity::Silver => "Silver",
+                Rarity::Gold => "Gold",
+                Rarity::Legendary => "Legendary",
+                Rarity::Promo => "Promo",
+                Rarity::Premium => "Premium",
+                Rarity::Special => "Special",
+                Rarity::SuperLegendary => "Super Legendary",
+                Rarity::UltimateRare => "Ultimate Rare",
+                Rarity::Other(rarity) => rarity,
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Card, CardCategory, CardClass, CardType, GameExtension, Rarity};
+    use crate::collection::CollectionCard;
+
+    /// Bronze follower of the `BP01` extension, named after its number
+    pub(crate) fn card(id: &str) -> Card {
+        Card {
+            id: id.to_string(),
+            name: id.to_string(),
+            card_class: CardClass::Neutral,
+            card_type: CardType::Follower,
+            rarity: Rarity::Bronze,
+            traits: Vec::new(),
+            hp: 1,
+            cost: 1,
+            attack: 1,
+            is_evolved: false,
+            category: CardCategory::Collectible,
+            evolution: None,
+            details: String::new(),
+            extension: GameExtension {
+                id: String::from("BP01"),
+                name: String::from("Advent of Genesis"),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub(crate) fn collection_card(card: Card, quantity: u8) -> CollectionCard {
+        CollectionCard {
+            card,
+            is_owned: quantity > 0,
+            quantity,
+            wanted: 0,
+            for_trade: 0,
+        }
+    }
+
+    #[test]
+    fn category_is_detected_from_the_type_and_the_number() {
+        assert_eq!(
+            CardCategory::detect(&CardType::Follower, "BP01-001"),
+            CardCategory::Collectible
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Token, "BP01-100"),
+            CardCategory::Token
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Amulet, "BP01-T05"),
+            CardCategory::Token
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Follower, "BP01-T01EN"),
+            CardCategory::Token
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Other(String::new()), "bp01-ld02"),
+            CardCategory::Leader
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Leader, "PR-001"),
+            CardCategory::Leader
+        );
+    }
+
+    #[test]
+    fn number_starting_with_a_t_is_not_always_a_token() {
+        assert_eq!(
+            CardCategory::detect(&CardType::Follower, "BP01-TX1"),
+            CardCategory::Collectible
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Follower, "BP01-T"),
+            CardCategory::Collectible
+        );
+        assert_eq!(
+            CardCategory::detect(&CardType::Spell, "BP01"),
+            CardCategory::Collectible
+        );
+    }
+
+    #[test]
+    fn rarity_is_read_from_its_name_or_its_abbreviation() {
+        let rarity = |value: &str| Rarity::from(value.to_string());
+
+        assert_eq!(rarity("Bronze"), Rarity::Bronze);
+        assert_eq!(rarity(" LG "), Rarity::Legendary);
+        assert_eq!(rarity("super legendary"), Rarity::SuperLegendary);
+        assert_eq!(rarity("SL"), Rarity::SuperLegendary);
+        assert_eq!(rarity("ur"), Rarity::UltimateRare);
+        assert_eq!(rarity(" Secret "), Rarity::Other(String::from("Secret")));
+        assert!(rarity("SP").is_variant());
+        assert!(!rarity("Gold").is_variant());
+    }
+}
@@ -1,4 +1,15 @@
-use crate::cards::{Card, GameExtension};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Card, GameExtension},
+    printing::{playset_shares, PlaysetShare},
+    stats::CategoryCompletion,
+};
 
 #[derive(Debug, Clone)]
 pub struct ExtensionProgression {
@@ -22,9 +33,151 @@ impl ExtensionProgression {
             .collect()
     }
 
+    /// Tokens and leaders of the extension
+    pub fn special_cards(&self) -> Vec<&CollectionCard> {
+        self.extension_cards
+            .iter()
+            .filter(|extension_card| !extension_card.card.category.is_collectible())
+            .collect()
+    }
+
+    /// Cards counted in the progression of the extension
+    pub fn counted_cards(
+        &self,
+        special_cards: SpecialCardsDisplay,
+    ) -> impl Iterator<Item = &CollectionCard> {
+        self.extension_cards
+            .iter()
+            .filter(move |extension_card| special_cards.counts(&extension_card.card))
+    }
+
     pub fn progression(&self) -> f32 {
-        let owned_cards_length = self.owned_cards().len() as f32;
-        owned_cards_length / self.extension_cards.len() as f32
+        self.progression_by(&ProgressionSettings::default())
+    }
+
+    /// Number of completed items and total number of items of the extension for the given mode,
+    /// the items are cards except for [`ProgressionMode::TotalCopies`] where they are copies
+    pub fn completion(&self, settings: &ProgressionSettings) -> (u32, u32) {
+        cards_completion(
+            self.counted_cards(settings.special_cards),
+            &self.playset_shares(settings),
+            settings.mode,
+        )
+    }
+
+    /// Share of the playset of each counted card, the alternate printings of a card count
+    /// toward the playset of its regular printing
+    pub fn playset_shares(&self, settings: &ProgressionSettings) -> HashMap<String, PlaysetShare> {
+        playset_shares(self.counted_cards(settings.special_cards), &settings.target)
+    }
+
+    /// Completion of the counted cards grouped by the given key, like the class or the rarity,
+    /// the groups without any item to complete are left out
+    pub fn completion_by<K: Ord + Display>(
+        &self,
+        settings: &ProgressionSettings,
+        key: impl Fn(&Card) -> K,
+    ) -> Vec<CategoryCompletion> {
+        let shares = self.playset_shares(settings);
+        let mut groups: BTreeMap<K, Vec<&CollectionCard>> = BTreeMap::new();
+        for extension_card in self.counted_cards(settings.special_cards) {
+            groups
+                .entry(key(&extension_card.card))
+                .or_default()
+                .push(extension_card);
+        }
+
+        groups
+            .into_iter()
+            .filter_map(|(key, cards)| {
+                let (owned, total) =
+                    cards_completion(cards.iter().copied(), &shares, settings.mode);
+                (total > 0).then(|| CategoryCompletion {
+                    name: key.to_string(),
+                    owned,
+                    total,
+                    copies: cards.iter().map(|card| card.quantity as u32).sum(),
+                })
+            })
+            .collect()
+    }
+
+    /// Counted cards of which no copy is owned
+    pub fn missing_cards(&self, special_cards: SpecialCardsDisplay) -> Vec<&CollectionCard> {
+        self.counted_cards(special_cards)
+            .filter(|extension_card| !extension_card.is_owned)
+            .collect()
+    }
+
+    /// List of the copies still needed, in the format of the trade lists
+    pub fn needed_cards_to_text(&self, settings: &ProgressionSettings) -> String {
+        let mut text = format!("[{}] {}\n", self.extension.id, self.extension.name);
+        for needed_card in self.needed_cards(settings) {
+            text.push_str(&format!(
+                "{}x {} {}\n",
+                needed_card.missing_copies,
+                needed_card.card.id,
+                needed_card.card.name()
+            ));
+        }
+
+        text
+    }
+
+    pub fn progression_by(&self, settings: &ProgressionSettings) -> f32 {
+        let (completed, total) = self.completion(settings);
+        if total == 0 {
+            return 0.0;
+        }
+
+        completed as f32 / total as f32
+    }
+
+    /// Cards of the extension with less copies than the target
+    pub fn needed_cards(&self, settings: &ProgressionSettings) -> Vec<NeededCard> {
+        let shares = self.playset_shares(settings);
+
+        self.counted_cards(settings.special_cards)
+            .filter_map(|extension_card| {
+                let share = share_of(&shares, extension_card);
+                (share.missing_copies() > 0).then(|| NeededCard {
+                    card: extension_card.card.clone(),
+                    quantity: share.owned,
+                    missing_copies: share.missing_copies(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Share of the playset of the card, nothing to collect when it is unknown
+pub fn share_of(shares: &HashMap<String, PlaysetShare>, card: &CollectionCard) -> PlaysetShare {
+    shares.get(&card.card.id).copied().unwrap_or_default()
+}
+
+/// Number of completed items and total number of items of the cards for the mode, the playsets
+/// are completed with the copies of every printing of a card
+fn cards_completion<'a>(
+    cards: impl Iterator<Item = &'a CollectionCard>,
+    shares: &HashMap<String, PlaysetShare>,
+    mode: ProgressionMode,
+) -> (u32, u32) {
+    match mode {
+        ProgressionMode::OwnedAtLeastOne => cards.fold((0, 0), |(owned, total), extension_card| {
+            (owned + extension_card.is_owned as u32, total + 1)
+        }),
+        ProgressionMode::CompletePlaysets => cards
+            .map(|extension_card| share_of(shares, extension_card))
+            .filter(|share| share.target > 0)
+            .fold((0, 0), |(completed, total), share| {
+                let is_complete = share.missing_copies() == 0;
+                (completed + is_complete as u32, total + 1)
+            }),
+        ProgressionMode::TotalCopies => cards
+            .map(|extension_card| share_of(shares, extension_card))
+            .fold((0, 0), |(owned, wanted), share| {
+                (owned + share.owned as u32, wanted + share.target as u32)
+            }),
     }
 }
 
@@ -33,4 +186,291 @@ pub struct CollectionCard {
     pub card: Card,
     pub is_owned: bool,
     pub quantity: u8,
+    /// Copies the user is looking for, on top of the ones missing for a playset
+    pub wanted: u8,
+    /// Copies the user is willing to trade, on top of the ones above a playset
+    pub for_trade: u8,
+}
+
+impl CollectionCard {
+    /// Copies the user is looking for: the ones missing for a playset and the wanted ones
+    pub fn wanted_copies(&self, share: &PlaysetShare) -> u8 {
+        share.missing_copies().saturating_add(self.wanted)
+    }
+
+    /// Copies the user can trade: the ones above a playset and the ones marked for trade
+    pub fn tradeable_copies(&self, share: &PlaysetShare) -> u8 {
+        share
+            .extra
+            .saturating_add(self.for_trade)
+            .min(self.quantity)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NeededCard {
+    pub card: Card,
+    pub quantity: u8,
+    pub missing_copies: u8,
+}
+
+/// Number of copies of each card the user wants to collect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaysetTarget {
+    pub copies: u8,
+    /// Evolved cards can not be put in a deck, so a single copy is usually enough
+    pub evolved_copies: u8,
+}
+
+impl PlaysetTarget {
+    pub fn for_card(&self, card: &Card) -> u8 {
+        if card.is_evolved {
+            self.evolved_copies
+        } else {
+            self.copies
+        }
+    }
+}
+
+impl Default for PlaysetTarget {
+    fn default() -> Self {
+        Self {
+            copies: 3,
+            evolved_copies: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgressionMode {
+    /// A card is collected as soon as one copy is owned
+    #[default]
+    OwnedAtLeastOne,
+    /// A card is collected when the target number of copies is owned
+    CompletePlaysets,
+    /// Count every owned copy against the total number of copies wanted
+    TotalCopies,
+}
+
+impl ProgressionMode {
+    pub const ALL: [ProgressionMode; 3] = [
+        ProgressionMode::OwnedAtLeastOne,
+        ProgressionMode::CompletePlaysets,
+        ProgressionMode::TotalCopies,
+    ];
+}
+
+impl std::fmt::Display for ProgressionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ProgressionMode::OwnedAtLeastOne => "Owned at least one",
+                ProgressionMode::CompletePlaysets => "Complete playsets",
+                ProgressionMode::TotalCopies => "Total copies",
+            }
+        )
+    }
+}
+
+/// How the tokens and the leaders are handled in the progression and in the cards lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpecialCardsDisplay {
+    /// Not counted in the progression, listed after the other cards
+    #[default]
+    Separated,
+    /// Not counted in the progression nor listed
+    Excluded,
+    /// Counted and listed like the other cards
+    Included,
+}
+
+impl SpecialCardsDisplay {
+    pub const ALL: [SpecialCardsDisplay; 3] = [
+        SpecialCardsDisplay::Separated,
+        SpecialCardsDisplay::Excluded,
+        SpecialCardsDisplay::Included,
+    ];
+
+    /// Whether the card is counted in the progression, the statistics and the trades
+    pub fn counts(&self, card: &Card) -> bool {
+        *self == SpecialCardsDisplay::Included || card.category.is_collectible()
+    }
+}
+
+impl std::fmt::Display for SpecialCardsDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SpecialCardsDisplay::Separated => "Tokens and leaders apart",
+                SpecialCardsDisplay::Excluded => "Hide tokens and leaders",
+                SpecialCardsDisplay::Included => "Count tokens and leaders",
+            }
+        )
+    }
+}
+
+/// How the progression of the extensions is computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgressionSettings {
+    pub mode: ProgressionMode,
+    pub target: PlaysetTarget,
+    pub special_cards: SpecialCardsDisplay,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExtensionProgression, PlaysetTarget, ProgressionMode, ProgressionSettings,
+        SpecialCardsDisplay,
+    };
+    use crate::cards::{
+        tests::{card, collection_card},
+        Card, CardCategory, GameExtension,
+    };
+
+    fn evolved_card(id: &str) -> Card {
+        Card {
+            is_evolved: true,
+            ..card(id)
+        }
+    }
+
+    fn token(id: &str) -> Card {
+        Card {
+            category: CardCategory::Token,
+            ..card(id)
+        }
+    }
+
+    fn extension(cards: Vec<(Card, u8)>) -> ExtensionProgression {
+        ExtensionProgression {
+            extension: GameExtension {
+                id: String::from("BP01"),
+                name: String::from("Advent of Genesis"),
+                ..Default::default()
+            },
+            extension_cards: cards
+                .into_iter()
+                .map(|(card, quantity)| collection_card(card, quantity))
+                .collect(),
+        }
+    }
+
+    /// A complete playset, an incomplete one, a missing card, a complete evolved card and a token
+    fn progression() -> ExtensionProgression {
+        extension(vec![
+            (card("BP01-001"), 3),
+            (card("BP01-002"), 1),
+            (card("BP01-003"), 0),
+            (evolved_card("BP01-004"), 1),
+            (token("BP01-T01"), 0),
+        ])
+    }
+
+    fn settings(mode: ProgressionMode) -> ProgressionSettings {
+        ProgressionSettings {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn completion_depends_on_the_mode() {
+        let progression = progression();
+
+        assert_eq!(
+            progression.completion(&settings(ProgressionMode::OwnedAtLeastOne)),
+            (3, 4)
+        );
+        assert_eq!(
+            progression.completion(&settings(ProgressionMode::CompletePlaysets)),
+            (2, 4)
+        );
+        assert_eq!(
+            progression.completion(&settings(ProgressionMode::TotalCopies)),
+            (5, 10)
+        );
+        assert_eq!(
+            progression.progression_by(&settings(ProgressionMode::TotalCopies)),
+            0.5
+        );
+    }
+
+    #[test]
+    fn special_cards_are_counted_when_they_are_included() {
+        let settings = ProgressionSettings {
+            special_cards: SpecialCardsDisplay::Included,
+            ..Default::default()
+        };
+
+        assert_eq!(progression().completion(&settings), (3, 5));
+    }
+
+    #[test]
+    fn evolved_cards_use_their_own_target() {
+        let target = PlaysetTarget {
+            copies: 4,
+            evolved_copies: 2,
+        };
+
+        assert_eq!(target.for_card(&card("BP01-001")), 4);
+        assert_eq!(target.for_card(&evolved_card("BP01-004")), 2);
+        assert_eq!(
+            PlaysetTarget::default().for_card(&evolved_card("BP01-004")),
+            1
+        );
+    }
+
+    #[test]
+    fn progression_is_zero_without_anything_to_collect() {
+        let only_tokens = extension(vec![(token("BP01-T01"), 1)]);
+        assert_eq!(
+            only_tokens.completion(&ProgressionSettings::default()),
+            (0, 0)
+        );
+        assert_eq!(
+            only_tokens.progression_by(&ProgressionSettings::default()),
+            0.0
+        );
+
+        let no_target = ProgressionSettings {
+            mode: ProgressionMode::CompletePlaysets,
+            target: PlaysetTarget {
+                copies: 0,
+                evolved_copies: 0,
+            },
+            ..Default::default()
+        };
+        assert_eq!(progression().completion(&no_target), (0, 0));
+        assert_eq!(progression().progression_by(&no_target), 0.0);
+    }
+
+    #[test]
+    fn needed_cards_are_the_incomplete_playsets() {
+        let needed_cards: Vec<(String, u8, u8)> = progression()
+            .needed_cards(&ProgressionSettings::default())
+            .into_iter()
+            .map(|needed_card| {
+                (
+                    needed_card.card.id,
+                    needed_card.quantity,
+                    needed_card.missing_copies,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            needed_cards,
+            vec![
+                (String::from("BP01-002"), 1, 2),
+                (String::from("BP01-003"), 0, 3)
+            ]
+        );
+    }
 }
@@ -1,94 +1,412 @@
-use std::{fs::File, io::Write, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{RwLock, RwLockReadGuard},
+};
 
 use serde::{Deserialize, Serialize};
 use serde_yaml;
 use thiserror::Error;
 
-use crate::environment;
+use crate::{
+    collection::ProgressionSettings,
+    environment::PathOverrides,
+    extension_mapping::{default_extension_mappings, ExtensionMapping},
+    release_date::ReleaseDate,
+    sort::ExtensionsListSettings,
+};
 
 pub struct Config {
+    pub config_file: PathBuf,
+    /// Directory of the database and the logs
+    pub data_directory: PathBuf,
+    /// Directory of the downloaded covers
+    pub cache_directory: PathBuf,
     pub db_file: PathBuf,
     pub covers_directory: PathBuf,
     pub log_file: PathBuf,
+    /// Every file is kept next to the executable
+    pub portable: bool,
+    /// Unknown keys and upgrades found while loading the configuration file
+    pub load_warnings: Vec<String>,
+    /// Content of the configuration file, updated when it is saved
+    configuration: RwLock<SerializedConfiguration>,
 }
 
 impl Config {
-    pub fn load() -> Result<Self, ConfigError> {
+    /// Load the configuration from the locations given on the command line, in the environment or
+    /// in the configuration file
+    pub fn load_with(overrides: PathOverrides) -> Result<Self, ConfigError> {
         // If the config do not exist, create it from the template in the root directory of the project
         // Get the path of the config file and open the file
-        let path = Self::config_path()?;
-        let config_file = File::open(path).unwrap();
+        let path = Self::config_path(&overrides)?;
+        let content = std::fs::read_to_string(&path)
+            .map_err(|error| ConfigError::Read(path.display().to_string(), error.to_string()))?;
+        let parse_error =
+            |error: serde_yaml::Error| ConfigError::Parse(format!("{}: {}", path.display(), error));
 
-        // Deserialize the config from the yaml file and handle parsing errors
-        let configuration: Result<SerializedConfiguration, serde_yaml::Error> =
-            serde_yaml::from_reader(config_file);
-
-        if let Err(error) = configuration {
-            return Err(ConfigError::Parse(error.to_string()));
+        // An empty file only contains default values
+        let mut document: serde_yaml::Value =
+            serde_yaml::from_str(&content).map_err(parse_error)?;
+        if document.is_null() {
+            document = serde_yaml::Value::Mapping(Default::default());
         }
 
-        let _configuration = configuration.unwrap();
+        // Bring the older files to the current schema and keep a copy of the original file
+        let mut load_warnings = overrides.warnings.clone();
+        let version = upgrade_configuration(&mut document, &mut load_warnings)
+            .map_err(|reason| ConfigError::Upgrade(path.display().to_string(), reason))?;
+        let content = if version < CONFIGURATION_VERSION {
+            let backup = path.with_extension(format!("v{}.yaml", version));
+            std::fs::copy(&path, &backup)
+                .map_err(|_| ConfigError::CreateFile(backup.display().to_string()))?;
+
+            let upgraded = edit_document(&content, &document).map_err(parse_error)?;
+            write_atomically(&path, &upgraded).map_err(|error| {
+                ConfigError::Write(path.display().to_string(), error.to_string())
+            })?;
+            load_warnings.push(format!(
+                "The configuration has been upgraded from the version {} to the version {}, the previous file has been saved in {}",
+                version,
+                CONFIGURATION_VERSION,
+                backup.display()
+            ));
+            upgraded
+        } else {
+            content
+        };
+
+        // Deserialize the config from the yaml file, the missing fields take their default value
+        let configuration: SerializedConfiguration =
+            serde_yaml::from_str(&content).map_err(parse_error)?;
+        configuration.validate()?;
+
+        let known_keys = serde_yaml::to_value(&configuration).map_err(parse_error)?;
+        unknown_keys(&document, &known_keys, "", &mut load_warnings);
+
+        // The command line and the environment take precedence over the configuration file
+        let data_directory = overrides
+            .data_directory
+            .clone()
+            .or_else(|| configuration.directories.data.clone())
+            .map(|directory| resolve_path(&path, directory))
+            .unwrap_or_else(|| overrides.root_directory());
+        let cache_directory = overrides
+            .cache_directory
+            .clone()
+            .or_else(|| configuration.directories.cache.clone())
+            .map(|directory| resolve_path(&path, directory))
+            .unwrap_or_else(|| overrides.default_cache_directory());
 
         // Create the config object
+        let mut config = Self::with_directories(
+            path,
+            configuration,
+            data_directory,
+            cache_directory,
+            overrides.portable,
+        )?;
+        config.load_warnings = load_warnings;
+
+        Ok(config)
+    }
+
+    fn with_directories(
+        config_file: PathBuf,
+        configuration: SerializedConfiguration,
+        data_directory: PathBuf,
+        cache_directory: PathBuf,
+        portable: bool,
+    ) -> Result<Self, ConfigError> {
+        let covers_directory = cache_directory.join(COVERS_DIRECTORY);
+        create_directory(&data_directory)?;
+        create_directory(&covers_directory)?;
+
         Ok(Self {
-            db_file: Self::db_file_path(),
-            covers_directory: Self::covers_directory().unwrap(),
-            log_file: Self::log_file_path(),
+            config_file,
+            db_file: data_directory.join(DB_FILE),
+            log_file: data_directory.join(LOGS_DIRECTORY),
+            covers_directory,
+            data_directory,
+            cache_directory,
+            portable,
+            load_warnings: Vec::new(),
+            configuration: RwLock::new(configuration),
         })
     }
 
-    fn config_path() -> Result<PathBuf, ConfigError> {
-        let dir = environment::config_directory().join("shadowverse-collection");
+    /// Move the database, the logs and the covers to new directories and save these directories
+    /// in the configuration file.
+    ///
+    /// The files are copied before the old ones are removed, nothing is removed if one of the
+    /// copies fails or if a file already exists in the new directories.
+    pub fn move_data(
+        &self,
+        data_directory: PathBuf,
+        cache_directory: PathBuf,
+    ) -> Result<Config, ConfigError> {
+        let resolved_data_directory = resolve_path(&self.config_file, data_directory.clone());
+        let resolved_cache_directory = resolve_path(&self.config_file, cache_directory.clone());
 
-        // Create the config directory if it does not exist
-        if !dir.exists() {
-            match std::fs::create_dir_all(dir.clone()) {
-                Ok(_) => {}
-                Err(error) => return Err(ConfigError::Create(error.to_string())),
+        let moves: Vec<(PathBuf, PathBuf)> = [
+            (&self.db_file, resolved_data_directory.join(DB_FILE)),
+            (&self.log_file, resolved_data_directory.join(LOGS_DIRECTORY)),
+            (
+                &self.covers_directory,
+                resolved_cache_directory.join(COVERS_DIRECTORY),
+            ),
+        ]
+        .into_iter()
+        .filter(|(from, to)| from.exists() && *from != to)
+        .map(|(from, to)| (from.clone(), to))
+        .collect();
+
+        // Check every destination before touching the files
+        for (from, to) in &moves {
+            if to.starts_with(from) {
+                return Err(ConfigError::Move(format!(
+                    "{} can not be moved inside itself",
+                    from.display()
+                )));
+            }
+            if to.exists() && !is_empty_directory(to) {
+                return Err(ConfigError::Move(format!(
+                    "{} already exists",
+                    to.display()
+                )));
             }
         }
 
-        let file_path = dir.join("config.yaml");
+        for (index, (from, to)) in moves.iter().enumerate() {
+            if let Err(error) = copy_recursively(from, to) {
+                // Remove the partial copies, the original files are still in place
+                for (_, copied) in &moves[..=index] {
+                    let _ = remove_path(copied);
+                }
+                return Err(ConfigError::Move(format!(
+                    "could not copy {}: {}",
+                    from.display(),
+                    error
+                )));
+            }
+        }
+
+        self.update_configuration(|configuration| {
+            configuration.directories = DirectoriesSettings {
+                data: Some(data_directory),
+                cache: Some(cache_directory),
+            }
+        })?;
+
+        for (from, _) in &moves {
+            if let Err(error) = remove_path(from) {
+                tracing::warn!("Could not remove {}: {}", from.display(), error);
+            }
+        }
+
+        Self::with_directories(
+            self.config_file.clone(),
+            self.configuration(),
+            resolved_data_directory,
+            resolved_cache_directory,
+            self.portable,
+        )
+    }
+
+    /// Content of the configuration file
+    pub fn configuration(&self) -> SerializedConfiguration {
+        self.loaded().clone()
+    }
+
+    fn loaded(&self) -> RwLockReadGuard<'_, SerializedConfiguration> {
+        self.configuration
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Validate the configuration and write it in the configuration file
+    pub fn save_configuration(
+        &self,
+        configuration: &SerializedConfiguration,
+    ) -> Result<(), ConfigError> {
+        configuration.validate()?;
+        self.update_configuration(|saved| {
+            *saved = SerializedConfiguration {
+                version: CONFIGURATION_VERSION,
+                ..configuration.clone()
+            }
+        })
+    }
+
+    /// Website the cards are downloaded from
+    pub fn source_url(&self) -> String {
+        self.loaded().shadowverse_api_url.clone()
+    }
+
+    pub fn sync_settings(&self) -> SyncSettings {
+        self.loaded().sync
+    }
+
+    /// Get the order and the width of the columns saved for a table
+    pub fn table_layout(&self, table: &str) -> Vec<ColumnLayout> {
+        self.loaded().tables.get(table).cloned().unwrap_or_default()
+    }
+
+    pub fn save_table_layout(
+        &self,
+        table: &str,
+        layout: Vec<ColumnLayout>,
+    ) -> Result<(), ConfigError> {
+        self.update_configuration(|configuration| {
+            configuration.tables.insert(table.to_string(), layout);
+        })
+    }
+
+    pub fn progression_settings(&self) -> ProgressionSettings {
+        self.loaded().progression
+    }
+
+    pub fn save_progression_settings(
+        &self,
+        settings: ProgressionSettings,
+    ) -> Result<(), ConfigError> {
+        self.update_configuration(|configuration| configuration.progression = settings)
+    }
+
+    pub fn extensions_list_settings(&self) -> ExtensionsListSettings {
+        self.loaded().extensions_list
+    }
+
+    pub fn save_extensions_list_settings(
+        &self,
+        settings: ExtensionsListSettings,
+    ) -> Result<(), ConfigError> {
+        self.update_configuration(|configuration| configuration.extensions_list = settings)
+    }
+
+    /// Extensions of the cards whose number prefix is not the id of their extension
+    pub fn extension_mappings(&self) -> Vec<ExtensionMapping> {
+        self.loaded().extension_mappings.clone()
+    }
+
+    /// Release dates of the extensions, the other extensions have no known date
+    pub fn release_dates(&self) -> Vec<ReleaseDate> {
+        self.loaded().release_dates.clone()
+    }
+
+    /// Apply the change to the configuration and write it in the configuration file, the loaded
+    /// configuration is only changed when the file has been written
+    fn update_configuration(
+        &self,
+        change: impl FnOnce(&mut SerializedConfiguration),
+    ) -> Result<(), ConfigError> {
+        let mut configuration = self
+            .configuration
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut changed = configuration.clone();
+        change(&mut changed);
+
+        self.write_configuration(&changed)?;
+        *configuration = changed;
+
+        Ok(())
+    }
+
+    /// Write the configuration in the configuration file. The sections whose values did not
+    /// change are kept as they are written, with their comments, the other sections are written
+    /// again without the comments they contain.
+    fn write_configuration(
+        &self,
+        configuration: &SerializedConfiguration,
+    ) -> Result<(), ConfigError> {
+        let path = self.config_file.display().to_string();
+        let content = std::fs::read_to_string(&self.config_file)
+            .map_err(|error| ConfigError::Read(path.clone(), error.to_string()))?;
+        let document = serde_yaml::to_value(configuration)
+            .map_err(|error| ConfigError::Parse(error.to_string()))?;
+        let edited = edit_document(&content, &document)
+            .map_err(|error| ConfigError::Parse(format!("{}: {}", path, error)))?;
+
+        write_atomically(&self.config_file, &edited)
+            .map_err(|error| ConfigError::Write(path, error.to_string()))
+    }
+
+    fn config_path(overrides: &PathOverrides) -> Result<PathBuf, ConfigError> {
+        let file_path = overrides
+            .config_file
+            .clone()
+            .unwrap_or_else(|| overrides.root_directory().join("config.yaml"));
+
+        // Create the config directory if it does not exist
+        if let Some(dir) = file_path.parent() {
+            create_directory(dir)?;
+        }
 
         // Create the config file using the template
         if !file_path.exists() {
-            let mut file = File::create(file_path.clone()).unwrap();
-            if let Err(error) = file.write_all(include_bytes!("../../config.yaml")) {
-                return Err(ConfigError::CreateFile(error.to_string()));
-            }
+            File::create(&file_path)
+                .and_then(|mut file| file.write_all(include_bytes!("../../config.yaml")))
+                .map_err(|error| {
+                    ConfigError::CreateFile(format!("{} ({})", file_path.display(), error))
+                })?;
         }
 
         Ok(file_path)
     }
+}
 
-    fn db_file_path() -> PathBuf {
-        environment::config_directory()
-            .join("shadowverse-collection")
-            .join("shadowverse_utils.db")
+const DB_FILE: &str = "shadowverse_utils.db";
+const LOGS_DIRECTORY: &str = "logs";
+const COVERS_DIRECTORY: &str = "covers";
+
+/// Relative directories are relative to the directory of the configuration file
+fn resolve_path(config_file: &Path, directory: PathBuf) -> PathBuf {
+    match config_file.parent() {
+        Some(parent) if directory.is_relative() => parent.join(directory),
+        _ => directory,
     }
+}
 
-    fn log_file_path() -> PathBuf {
-        environment::config_directory()
-            .join("shadowverse-collection")
-            .join("logs")
+fn create_directory(directory: &Path) -> Result<(), ConfigError> {
+    if !directory.exists() {
+        std::fs::create_dir_all(directory)
+            .map_err(|error| ConfigError::Create(format!("{} ({})", directory.display(), error)))?;
     }
 
-    fn covers_directory() -> Result<PathBuf, ConfigError> {
-        let covers_dir = environment::local_directory()
-            .join("shadowverse-collection")
-            .join("covers");
+    Ok(())
+}
 
-        // Create the config directory if it does not exist
-        if !covers_dir.exists() {
-            match std::fs::create_dir_all(covers_dir.clone()) {
-                Ok(_) => {}
-                Err(_error) => {
-                    return Err(ConfigError::Create(covers_dir.to_str().unwrap().to_owned()))
-                }
-            }
+fn is_empty_directory(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
         }
+    } else {
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(from, to)?;
+    }
 
-        Ok(covers_dir)
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
     }
 }
 
@@ -96,13 +414,478 @@ impl Config {
 pub enum ConfigError {
     #[error("{0}")]
     Parse(String),
+    #[error("Invalid {0}: {1}")]
+    Invalid(String, String),
+    #[error("Could not read the file {0}: {1}")]
+    Read(String, String),
+    #[error("Could not write the file {0}: {1}")]
+    Write(String, String),
+    #[error("Could not upgrade the configuration file {0}: {1}")]
+    Upgrade(String, String),
     #[error("Could not create the directory {0}")]
     Create(String),
     #[error("Could not create the file {0}")]
     CreateFile(String),
+    #[error("Could not move the data: {0}")]
+    Move(String),
+}
+
+pub const DEFAULT_SOURCE_URL: &str = "https://en.shadowverse-evolve.com";
+const LEGACY_API_URL: &str = "http://localhost:8081";
+/// Version of the schema written in the configuration files
+pub const CONFIGURATION_VERSION: u32 = 1;
+
+/// Apply the upgrades needed to bring the document to the current schema, returns the version the
+/// document had before the upgrades
+fn upgrade_configuration(
+    document: &mut serde_yaml::Value,
+    warnings: &mut Vec<String>,
+) -> Result<u32, String> {
+    let Some(mapping) = document.as_mapping_mut() else {
+        return Err(String::from("the file does not contain a list of settings"));
+    };
+
+    // The files written before the versioning do not have a version
+    let version = match mapping.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("{:?} is not a valid version", version))?,
+    };
+
+    if version > CONFIGURATION_VERSION {
+        warnings.push(format!(
+            "The configuration file has been written by a newer version of the application (version {}), some settings may be ignored",
+            version
+        ));
+        return Ok(version);
+    }
+
+    if version < 1 {
+        // The template used to point to a local API that is not used anymore
+        let url = mapping
+            .get("shadowverse_api_url")
+            .and_then(|url| url.as_str());
+        if url == Some(LEGACY_API_URL) {
+            mapping.insert(
+                "shadowverse_api_url".into(),
+                DEFAULT_SOURCE_URL.to_string().into(),
+            );
+        }
+    }
+
+    mapping.insert("version".into(), CONFIGURATION_VERSION.into());
+
+    Ok(version)
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct SerializedConfiguration {
-    shadowverse_api_url: String,
+/// List the keys of the document that are not part of the schema
+fn unknown_keys(
+    document: &serde_yaml::Value,
+    known: &serde_yaml::Value,
+    parent: &str,
+    warnings: &mut Vec<String>,
+) {
+    let (Some(document), Some(known)) = (document.as_mapping(), known.as_mapping()) else {
+        return;
+    };
+
+    for (key, value) in document {
+        let name = match key.as_str() {
+            Some(key) if parent.is_empty() => key.to_string(),
+            Some(key) => format!("{}.{}", parent, key),
+            None => continue,
+        };
+
+        match known.get(key) {
+            Some(known) => unknown_keys(value, known, &name, warnings),
+            // Empty values are not written back
+            None if value.is_null() => {}
+            None => warnings.push(format!("Unknown configuration key {} ignored", name)),
+        }
+    }
+}
+
+/// Write the document over the content of the configuration file. Each top level setting whose
+/// value did not change keeps the text it has in the file, with its comments, the settings that
+/// changed are written again without the comments they contained and the new settings are added at
+/// the end of the file when they do not have their default value. The settings of the file that
+/// are not in the document are kept.
+fn edit_document(content: &str, document: &serde_yaml::Value) -> Result<String, serde_yaml::Error> {
+    let Some(settings) = document.as_mapping() else {
+        return serde_yaml::to_string(document);
+    };
+    let write_setting = |key: &serde_yaml::Value, value: &serde_yaml::Value| {
+        let mut setting = serde_yaml::Mapping::new();
+        setting.insert(key.clone(), value.clone());
+        serde_yaml::to_string(&setting)
+    };
+
+    // A setting starts on a line without indentation that is not a comment or a list item, and
+    // ends before the comments and the empty lines that precede the next setting
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let is_setting_start =
+        |line: &str| !line.trim().is_empty() && !line.starts_with([' ', '\t', '#', '-']);
+    let is_between_settings = |line: &str| line.trim().is_empty() || line.starts_with('#');
+    let starts: Vec<usize> = (0..lines.len())
+        .filter(|&index| is_setting_start(lines[index]))
+        .collect();
+
+    let mut edited = lines[..starts.first().copied().unwrap_or(lines.len())].concat();
+    let mut written = Vec::new();
+    for (position, &start) in starts.iter().enumerate() {
+        let next_start = starts.get(position + 1).copied().unwrap_or(lines.len());
+        let mut end = next_start;
+        while end > start + 1 && is_between_settings(lines[end - 1]) {
+            end -= 1;
+        }
+        let text = lines[start..end].concat();
+
+        // The settings the file can not be split into are all written again
+        let Ok(serde_yaml::Value::Mapping(previous)) = serde_yaml::from_str(&text) else {
+            return serde_yaml::to_string(document);
+        };
+        let Some((key, previous)) = previous.into_iter().next() else {
+            return serde_yaml::to_string(document);
+        };
+
+        match settings.get(&key) {
+            Some(value) if *value != previous => {
+                edited.push_str(&write_setting(&key, value)?);
+                written.push(key);
+            }
+            Some(_) => {
+                edited.push_str(&text);
+                written.push(key);
+            }
+            None => edited.push_str(&text),
+        }
+        edited.push_str(&lines[end..next_start].concat());
+    }
+
+    // The version is always written so the file is not upgraded again
+    let defaults = serde_yaml::to_value(SerializedConfiguration::default())?;
+    for (key, value) in settings {
+        if written.contains(key) || (key != "version" && defaults.get(key) == Some(value)) {
+            continue;
+        }
+
+        if !edited.is_empty() {
+            if !edited.ends_with('\n') {
+                edited.push('\n');
+            }
+            edited.push('\n');
+        }
+        edited.push_str(&write_setting(key, value)?);
+    }
+
+    Ok(edited)
+}
+
+/// Write the content in a temporary file next to the file then replace the file with it, so the
+/// file is never left half written
+fn write_atomically(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    let temporary_path = path.with_file_name(file_name);
+
+    let written = File::create(&temporary_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temporary_path, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temporary_path);
+    }
+
+    written
+}
+
+/// Content of the `config.yaml` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SerializedConfiguration {
+    /// Version of the schema of the file, used to upgrade the older files
+    pub version: u32,
+    /// Website the cards are downloaded from
+    pub shadowverse_api_url: String,
+    pub theme: String,
+    pub language: Language,
+    pub sync: SyncSettings,
+    pub directories: DirectoriesSettings,
+    pub tables: HashMap<String, Vec<ColumnLayout>>,
+    pub progression: ProgressionSettings,
+    pub extensions_list: ExtensionsListSettings,
+    pub extension_mappings: Vec<ExtensionMapping>,
+    pub release_dates: Vec<ReleaseDate>,
+}
+
+impl SerializedConfiguration {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let url = self.shadowverse_api_url.trim();
+        if !(url.starts_with("https://") || url.starts_with("http://")) || url.contains(' ') {
+            return Err(ConfigError::Invalid(
+                String::from("source URL"),
+                format!("{} is not an http or https address", url),
+            ));
+        }
+
+        if self.theme.trim().is_empty() {
+            return Err(ConfigError::Invalid(
+                String::from("theme"),
+                String::from("the name can not be empty"),
+            ));
+        }
+
+        if let Some(mapping) = self
+            .extension_mappings
+            .iter()
+            .find(|mapping| mapping.prefix.is_empty() || mapping.extension_id.is_empty())
+        {
+            return Err(ConfigError::Invalid(
+                String::from("extension mapping"),
+                format!(
+                    "{} -> {} needs a prefix and an extension",
+                    mapping.prefix, mapping.extension_id
+                ),
+            ));
+        }
+
+        if let Some(release_date) = self
+            .release_dates
+            .iter()
+            .find(|release_date| !release_date.is_valid())
+        {
+            return Err(ConfigError::Invalid(
+                String::from("release date"),
+                format!(
+                    "{} of {} is not written as YYYY-MM-DD",
+                    release_date.date, release_date.extension_id
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for SerializedConfiguration {
+    fn default() -> Self {
+        Self {
+            version: CONFIGURATION_VERSION,
+            shadowverse_api_url: DEFAULT_SOURCE_URL.to_string(),
+            theme: String::from("dark"),
+            language: Language::default(),
+            sync: SyncSettings::default(),
+            directories: DirectoriesSettings::default(),
+            tables: HashMap::new(),
+            progression: ProgressionSettings::default(),
+            extensions_list: ExtensionsListSettings::default(),
+            extension_mappings: default_extension_mappings(),
+            release_dates: Vec::new(),
+        }
+    }
+}
+
+/// Language of the cards, only the english website is supported by the updater
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 1] = [Language::English];
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Language::English => "English",
+            }
+        )
+    }
+}
+
+/// When the cards are downloaded from the website
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    /// Look for the new cards each time the application starts
+    pub on_startup: bool,
+    /// Download the cover of the new cards with their details
+    pub download_covers: bool,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            on_startup: true,
+            download_covers: true,
+        }
+    }
+}
+
+/// Locations of the files of the application, the default directories of the system are used when
+/// they are not set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectoriesSettings {
+    /// Directory of the database and the logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<PathBuf>,
+    /// Directory of the downloaded covers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<PathBuf>,
+}
+
+/// Position and width of a column of a table, the columns are saved in their display order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub name: String,
+    /// Width in pixels of the column when it has been resized by the user
+    pub width: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        edit_document, unknown_keys, upgrade_configuration, SerializedConfiguration,
+        CONFIGURATION_VERSION, DEFAULT_SOURCE_URL, LEGACY_API_URL,
+    };
+    use crate::release_date::ReleaseDate;
+
+    const TEMPLATE: &str = include_str!("../../config.yaml");
+
+    fn document(content: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn unversioned_file_is_upgraded() {
+        let mut document = document(&format!(
+            "shadowverse_api_url: {}\ntheme: light",
+            LEGACY_API_URL
+        ));
+        let mut warnings = Vec::new();
+
+        assert_eq!(upgrade_configuration(&mut document, &mut warnings), Ok(0));
+
+        assert_eq!(
+            document["version"].as_u64(),
+            Some(CONFIGURATION_VERSION as u64)
+        );
+        assert_eq!(
+            document["shadowverse_api_url"].as_str(),
+            Some(DEFAULT_SOURCE_URL)
+        );
+        assert_eq!(document["theme"].as_str(), Some("light"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn custom_source_url_is_kept() {
+        let mut document = document("shadowverse_api_url: https://example.com");
+
+        upgrade_configuration(&mut document, &mut Vec::new()).unwrap();
+
+        assert_eq!(
+            document["shadowverse_api_url"].as_str(),
+            Some("https://example.com")
+        );
+    }
+
+    #[test]
+    fn newer_file_is_left_untouched_with_a_warning() {
+        let newer_version = CONFIGURATION_VERSION + 1;
+        let mut document = document(&format!("version: {}", newer_version));
+        let mut warnings = Vec::new();
+
+        assert_eq!(
+            upgrade_configuration(&mut document, &mut warnings),
+            Ok(newer_version)
+        );
+
+        assert_eq!(document["version"].as_u64(), Some(newer_version as u64));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn invalid_documents_are_rejected() {
+        assert!(upgrade_configuration(&mut document("- theme"), &mut Vec::new()).is_err());
+        assert!(upgrade_configuration(&mut document("version: first"), &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_with_their_path() {
+        let document = document("theme: dark\nthme: light\nsync:\n  on_start: true");
+        let known_keys = serde_yaml::to_value(SerializedConfiguration::default()).unwrap();
+        let mut warnings = Vec::new();
+
+        unknown_keys(&document, &known_keys, "", &mut warnings);
+
+        assert_eq!(
+            warnings,
+            vec![
+                "Unknown configuration key thme ignored",
+                "Unknown configuration key sync.on_start ignored"
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_changed_settings_are_written_again() {
+        let mut configuration: SerializedConfiguration = serde_yaml::from_str(TEMPLATE).unwrap();
+        configuration.theme = String::from("light");
+        configuration.sync.on_startup = false;
+
+        let edited =
+            edit_document(TEMPLATE, &serde_yaml::to_value(&configuration).unwrap()).unwrap();
+
+        assert!(edited.contains("\ntheme: light\n"));
+        assert!(!edited.contains("# Look for the new cards each time the application starts"));
+        assert!(edited.contains("# Website the cards are downloaded from"));
+        assert!(edited.contains("#   # Database and logs"));
+        assert!(edited.contains("# release_dates:"));
+        assert_eq!(
+            TEMPLATE
+                .replace("\ntheme: \"dark\"\n", "\ntheme: light\n")
+                .lines()
+                .count(),
+            edited.lines().count() + 2
+        );
+        let written: SerializedConfiguration = serde_yaml::from_str(&edited).unwrap();
+        assert_eq!(
+            serde_yaml::to_value(written).unwrap(),
+            serde_yaml::to_value(&configuration).unwrap()
+        );
+    }
+
+    #[test]
+    fn new_settings_are_added_when_they_are_not_the_default() {
+        let configuration = SerializedConfiguration {
+            release_dates: vec![ReleaseDate {
+                extension_id: String::from("BP01"),
+                date: String::from("2023-10-27"),
+            }],
+            ..Default::default()
+        };
+
+        let edited = edit_document(
+            "# Theme of the interface\ntheme: dark\nthme: light\n",
+            &serde_yaml::to_value(&configuration).unwrap(),
+        )
+        .unwrap();
+
+        assert!(edited.starts_with("# Theme of the interface\ntheme: dark\nthme: light\n\n"));
+        assert!(edited.contains("release_dates:\n"));
+        assert!(edited.contains(&format!("version: {}\n", CONFIGURATION_VERSION)));
+        assert!(!edited.contains("progression:"));
+        assert!(!edited.contains("extension_mappings:"));
+    }
 }
@@ -0,0 +1,219 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use image::imageops::FilterType;
+use rusqlite::{
+    types::{FromSql, ValueRef},
+    ToSql,
+};
+use thiserror::Error;
+
+use crate::{config::Config, db};
+
+/// Width in pixels of the thumbnails, the height keeps the ratio of the cover
+pub const THUMBNAIL_WIDTH: u32 = 160;
+
+const THUMBNAILS_DIRECTORY: &str = "thumbnails";
+
+#[derive(Debug, Error)]
+pub enum CoverError {
+    #[error("The cover of the card {0} has not been downloaded")]
+    Missing(String),
+    #[error("Could not create the thumbnail of the card {0}: {1}")]
+    Thumbnail(String, String),
+    #[error("Could not update the image cache: {0}")]
+    Cache(String),
+}
+
+/// Images of the cards stored in the covers directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// Cover as downloaded from the website
+    Cover,
+    Thumbnail,
+}
+
+impl ImageKind {
+    pub const ALL: [ImageKind; 2] = [ImageKind::Cover, ImageKind::Thumbnail];
+
+    pub fn path(&self, covers_directory: &Path, card_id: &str) -> PathBuf {
+        match self {
+            ImageKind::Cover => cover_path(covers_directory, card_id),
+            ImageKind::Thumbnail => thumbnail_path(covers_directory, card_id),
+        }
+    }
+
+    /// Directory containing the images of this kind
+    fn directory(&self, covers_directory: &Path) -> PathBuf {
+        match self {
+            ImageKind::Cover => covers_directory.to_path_buf(),
+            ImageKind::Thumbnail => covers_directory.join(THUMBNAILS_DIRECTORY),
+        }
+    }
+}
+
+impl Display for ImageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ImageKind::Cover => "Cover",
+                ImageKind::Thumbnail => "Thumbnail",
+            }
+        )
+    }
+}
+
+impl FromSql for ImageKind {
+    fn column_result(value: ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        String::column_result(value).map(|kind| match kind.as_str() {
+            "Thumbnail" => ImageKind::Thumbnail,
+            _ => ImageKind::Cover,
+        })
+    }
+}
+
+impl ToSql for ImageKind {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.to_string().into())
+    }
+}
+
+/// Number of files and disk space used by the images of a kind
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheUsage {
+    pub files: u32,
+    pub bytes: u64,
+}
+
+impl CacheUsage {
+    /// Size of the files in a human readable unit
+    pub fn size(&self) -> String {
+        const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+        let mut size = self.bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImageCacheStats {
+    pub covers: CacheUsage,
+    pub thumbnails: CacheUsage,
+}
+
+/// Path of the cover of a card, as downloaded from the website
+pub fn cover_path(covers_directory: &Path, card_id: &str) -> PathBuf {
+    covers_directory.join(format!("{}.png", card_id))
+}
+
+pub fn thumbnail_path(covers_directory: &Path, card_id: &str) -> PathBuf {
+    covers_directory
+        .join(THUMBNAILS_DIRECTORY)
+        .join(format!("{}.png", card_id))
+}
+
+/// Thumbnail of the card when it has been generated, otherwise its cover when it has been
+/// downloaded
+pub fn displayed_cover(covers_directory: &Path, card_id: &str) -> Option<PathBuf> {
+    [
+        thumbnail_path(covers_directory, card_id),
+        cover_path(covers_directory, card_id),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+/// Create the thumbnail of the cover of a card if it does not exist yet
+pub fn generate_thumbnail(covers_directory: &Path, card_id: &str) -> Result<PathBuf, CoverError> {
+    let thumbnail = thumbnail_path(covers_directory, card_id);
+    if thumbnail.exists() {
+        return Ok(thumbnail);
+    }
+
+    let cover = cover_path(covers_directory, card_id);
+    if !cover.exists() {
+        return Err(CoverError::Missing(card_id.to_string()));
+    }
+
+    let thumbnail_error =
+        |error: &dyn ToString| CoverError::Thumbnail(card_id.to_string(), error.to_string());
+
+    if let Some(thumbnails_directory) = thumbnail.parent() {
+        std::fs::create_dir_all(thumbnails_directory).map_err(|error| thumbnail_error(&error))?;
+    }
+
+    let image = image::open(&cover).map_err(|error| thumbnail_error(&error))?;
+    let height = image.height() * THUMBNAIL_WIDTH / image.width().max(1);
+    image
+        .resize(THUMBNAIL_WIDTH, height, FilterType::Triangle)
+        .save(&thumbnail)
+        .map_err(|error| thumbnail_error(&error))?;
+
+    Ok(thumbnail)
+}
+
+/// Record the images of a card present in the covers directory
+pub fn track_card_images(config: &Config, card_id: &str) -> Result<(), CoverError> {
+    for kind in ImageKind::ALL {
+        let path = kind.path(&config.covers_directory, card_id);
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            db::save_cached_image(config, card_id, kind, metadata.len())
+                .map_err(|error| CoverError::Cache(error.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Make the tracked images match the files of the covers directory, they can be created or
+/// removed outside of the application
+pub fn sync_image_cache(config: &Config) -> Result<ImageCacheStats, CoverError> {
+    db::clear_cached_images(config, None).map_err(|error| CoverError::Cache(error.to_string()))?;
+
+    for kind in ImageKind::ALL {
+        let Ok(entries) = std::fs::read_dir(kind.directory(&config.covers_directory)) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_image = path.extension().is_some_and(|extension| extension == "png");
+            let (Some(card_id), Ok(metadata)) = (path.file_stem(), entry.metadata()) else {
+                continue;
+            };
+            if !is_image || !metadata.is_file() {
+                continue;
+            }
+
+            db::save_cached_image(config, &card_id.to_string_lossy(), kind, metadata.len())
+                .map_err(|error| CoverError::Cache(error.to_string()))?;
+        }
+    }
+
+    db::get_image_cache_stats(config).map_err(|error| CoverError::Cache(error.to_string()))
+}
+
+/// Delete the images of the given kind, or every image, from the disk and from the cache
+pub fn clear_image_cache(config: &Config, kind: Option<ImageKind>) -> Result<(), CoverError> {
+    let images = db::get_cached_images(config, kind)
+        .map_err(|error| CoverError::Cache(error.to_string()))?;
+
+    for (card_id, image_kind) in images {
+        let path = image_kind.path(&config.covers_directory, &card_id);
+        if let Err(error) = std::fs::remove_file(&path) {
+            tracing::warn!("Could not delete {}: {}", path.display(), error);
+        }
+    }
+
+    db::clear_cached_images(config, kind).map_err(|error| CoverError::Cache(error.to_string()))
+}
@@ -1,13 +1,31 @@
 use crate::{
-    cards::{Card, GameExtension},
-    collection::{CollectionCard, ExtensionProgression},
+    cards::{parse_traits, Card, CardCategory, CardType, GameExtension, Rarity, SetType},
+    collection::{CollectionCard, ExtensionProgression, SpecialCardsDisplay},
     config::Config,
+    covers::{CacheUsage, ImageCacheStats, ImageKind},
+    extension_mapping::{extension_id, ExtensionMapping},
+    filter::CardsFilter,
+    release_date::{release_date, ReleaseDate},
+    sort::CardsSort,
+    stats::{CategoryCompletion, CollectionStats, RecentExtension},
 };
-use rusqlite::Connection;
+use rusqlite::{Connection, Row};
+use thiserror::Error;
 
-pub fn setup_db(config: &Config) -> Result<(), ()> {
-    let connection =
-        Connection::open(config.db_file.clone()).expect("Could open the database file");
+const SEARCH_RESULTS_LIMIT: usize = 200;
+const RECENT_EXTENSIONS_LIMIT: usize = 5;
+
+#[derive(Debug, Error)]
+pub enum DbError {
+    #[error("Could not setup the database: {0}")]
+    Setup(String),
+    #[error("Could not execute the query: {0}")]
+    Query(String),
+}
+
+pub fn setup_db(config: &Config) -> Result<(), DbError> {
+    let connection = Connection::open(config.db_file.clone())
+        .map_err(|error| DbError::Setup(format!("{} ({})", config.db_file.display(), error)))?;
 
     let db_setup_result = connection.execute_batch(
         "BEGIN;
@@ -36,20 +54,470 @@ pub fn setup_db(config: &Config) -> Result<(), ()> {
             FOREIGN KEY (card_id) REFERENCES card (id)
         );
         CREATE UNIQUE INDEX IF NOT EXISTS collected_cards_card_id_IDX ON collected_cards (card_id);
-        ALTER TABLE collected_cards ADD COLUMN quantity INTEGER DEFAULT 0;
+        CREATE TABLE IF NOT EXISTS extension_filter (
+            extension_id VARCHAR(50) PRIMARY KEY,
+            filter TEXT NOT NULL,
+            FOREIGN KEY (extension_id) REFERENCES extension (id)
+        );
+        CREATE TABLE IF NOT EXISTS trait (
+            id INTEGER PRIMARY KEY,
+            name VARCHAR(100) NOT NULL UNIQUE
+        );
+        CREATE TABLE IF NOT EXISTS card_trait (
+            card_id TEXT NOT NULL,
+            trait_id INTEGER NOT NULL,
+            PRIMARY KEY (card_id, trait_id),
+            FOREIGN KEY (card_id) REFERENCES card (id),
+            FOREIGN KEY (trait_id) REFERENCES trait (id)
+        );
+        CREATE TABLE IF NOT EXISTS card_evolution (
+            evolved_card_id TEXT PRIMARY KEY,
+            base_card_id TEXT NOT NULL,
+            FOREIGN KEY (evolved_card_id) REFERENCES card (id),
+            FOREIGN KEY (base_card_id) REFERENCES card (id)
+        );
+        CREATE TABLE IF NOT EXISTS cached_image (
+            card_id TEXT NOT NULL,
+            kind VARCHAR(20) NOT NULL,
+            size INTEGER NOT NULL,
+            cached_at INTEGER NOT NULL,
+            PRIMARY KEY (card_id, kind)
+        );
+        CREATE TABLE IF NOT EXISTS screen_sort (
+            screen VARCHAR(50) PRIMARY KEY,
+            sort TEXT NOT NULL
+        );
         COMMIT;",
     );
 
     if let Err(error) = db_setup_result {
         tracing::error!("{:?}", error);
-        return Err(());
+        return Err(DbError::Setup(error.to_string()));
     }
 
+    add_column_if_missing(
+        &connection,
+        "collected_cards",
+        "quantity",
+        "INTEGER DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        &connection,
+        "collected_cards",
+        "wanted",
+        "INTEGER DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        &connection,
+        "collected_cards",
+        "for_trade",
+        "INTEGER DEFAULT 0",
+    )?;
+    add_column_if_missing(&connection, "collected_cards", "updated_at", "INTEGER")?;
+    setup_card_categories(&connection)?;
+    normalize_card_columns(&connection)?;
+    add_column_if_missing(&connection, "extension", "set_type", "VARCHAR(50)")?;
+    add_column_if_missing(&connection, "extension", "release_date", "TEXT")?;
+    add_column_if_missing(&connection, "extension", "card_count", "INTEGER DEFAULT 0")?;
+    apply_extension_mappings(&connection, &config.extension_mappings())?;
+    setup_extensions_metadata(&connection)?;
+    apply_release_dates(&connection, &config.release_dates())?;
+    setup_search_index(&connection)?;
+    setup_traits(&connection)?;
+    link_cards_evolutions(&connection).map_err(|error| DbError::Setup(error.to_string()))?;
+
     let _ = connection.close();
 
     Ok(())
 }
 
+fn add_column_if_missing(
+    connection: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), DbError> {
+    let column_exists = connection
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+            (table, column),
+            |row| row.get::<_, u32>(0),
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?
+        > 0;
+
+    if column_exists {
+        return Ok(());
+    }
+
+    connection
+        .execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Store the category of the cards, the cards downloaded before it existed get the one of their
+/// type
+fn setup_card_categories(connection: &Connection) -> Result<(), DbError> {
+    let column_exists = connection
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('card') WHERE name = 'category'",
+            [],
+            |row| row.get::<_, u32>(0),
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?
+        > 0;
+    if column_exists {
+        return Ok(());
+    }
+
+    // The categories are detected the same way as for the downloaded cards
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    transaction
+        .execute(
+            "ALTER TABLE card ADD COLUMN category VARCHAR(50) NOT NULL DEFAULT 'Collectible'",
+            [],
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    let mut statement = transaction
+        .prepare("SELECT id, type FROM card")
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    let cards: Vec<(String, CardType)> = statement
+        .query_map([], |row| Ok((row.get("id")?, row.get("type")?)))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    drop(statement);
+
+    for (card_id, card_type) in cards {
+        let category = CardCategory::detect(&card_type, &card_id);
+        if category.is_collectible() {
+            continue;
+        }
+
+        transaction
+            .execute(
+                "UPDATE card SET category = ? WHERE id = ?",
+                (&category, &card_id),
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    transaction
+        .commit()
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Rewrite the type, rarity and traits of the cards saved as displayed on the website to the
+/// values written by the current version
+fn normalize_card_columns(connection: &Connection) -> Result<(), DbError> {
+    for column in ["type", "rarity", "trait"] {
+        let mut statement = connection
+            .prepare(&format!("SELECT DISTINCT {column} FROM card"))
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+        let values: Vec<String> = statement
+            .query_map([], |row| row.get(0))
+            .and_then(Iterator::collect)
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+
+        for value in values {
+            let normalized = match column {
+                "type" => CardType::from(value.clone()).to_string(),
+                "rarity" => Rarity::from(value.clone()).to_string(),
+                _ => parse_traits(&value).join(" / "),
+            };
+            if normalized == value {
+                continue;
+            }
+
+            tracing::info!("Normalizing the {} {} to {}", column, value, normalized);
+            connection
+                .execute(
+                    &format!("UPDATE card SET {column} = ?1 WHERE {column} = ?2"),
+                    (&normalized, &value),
+                )
+                .map_err(|error| DbError::Setup(error.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move the cards downloaded in the extension of their number prefix to the extension given by
+/// the mappings, and remove the extensions left without cards
+fn apply_extension_mappings(
+    connection: &Connection,
+    mappings: &[ExtensionMapping],
+) -> Result<(), DbError> {
+    let mut statement = connection
+        .prepare("SELECT id, extension_id FROM card")
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    let cards: Vec<(String, Option<String>)> = statement
+        .query_map([], |row| Ok((row.get("id")?, row.get("extension_id")?)))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    // The cards without extension have no extension to be moved from
+    let misplaced_cards: Vec<(&str, &str, &str)> = cards
+        .iter()
+        .filter_map(|(card_id, current_extension_id)| {
+            let current_extension_id = current_extension_id.as_ref()?;
+            let mapped_extension_id = extension_id(card_id, mappings);
+            (mapped_extension_id != current_extension_id).then_some((
+                card_id.as_str(),
+                current_extension_id.as_str(),
+                mapped_extension_id,
+            ))
+        })
+        .collect();
+    if misplaced_cards.is_empty() {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Moving {} cards to the extensions of the mappings",
+        misplaced_cards.len()
+    );
+    for (card_id, current_extension_id, mapped_extension_id) in misplaced_cards {
+        // The extension keeps the name of the first one it is created from
+        connection
+            .execute(
+                "INSERT OR IGNORE INTO extension (id, name, set_type, release_date)
+                SELECT ?1, name, ?2, release_date FROM extension WHERE id = ?3",
+                (
+                    mapped_extension_id,
+                    SetType::from_extension_id(mapped_extension_id),
+                    current_extension_id,
+                ),
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+        connection
+            .execute(
+                "UPDATE card SET extension_id = ? WHERE id = ?",
+                (mapped_extension_id, card_id),
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    connection
+        .execute_batch(
+            "BEGIN;
+            DELETE FROM extension_filter WHERE extension_id NOT IN (
+                SELECT DISTINCT extension_id FROM card WHERE extension_id IS NOT NULL
+            );
+            DELETE FROM extension WHERE id NOT IN (
+                SELECT DISTINCT extension_id FROM card WHERE extension_id IS NOT NULL
+            );
+            COMMIT;",
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Write the release dates of the configuration, the extensions without a date are left unknown
+fn apply_release_dates(
+    connection: &Connection,
+    release_dates: &[ReleaseDate],
+) -> Result<(), DbError> {
+    let mut statement = connection
+        .prepare("SELECT id FROM extension")
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    let extensions_ids: Vec<String> = statement
+        .query_map([], |row| row.get("id"))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    for extension_id in extensions_ids {
+        connection
+            .execute(
+                "UPDATE extension SET release_date = ? WHERE id = ?",
+                (release_date(&extension_id, release_dates), &extension_id),
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Fill the set type and the number of cards of the extensions downloaded before they were stored
+fn setup_extensions_metadata(connection: &Connection) -> Result<(), DbError> {
+    let mut statement = connection
+        .prepare("SELECT id FROM extension WHERE set_type IS NULL")
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    let extensions_ids: Vec<String> = statement
+        .query_map([], |row| row.get("id"))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    for extension_id in extensions_ids {
+        connection
+            .execute(
+                "UPDATE extension SET set_type = ? WHERE id = ?",
+                (SetType::from_extension_id(&extension_id), &extension_id),
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    connection
+        .execute(
+            "UPDATE extension SET card_count = (
+                SELECT COUNT(*)
+                FROM card
+                WHERE card.extension_id = extension.id AND card.category = 'Collectible'
+            )",
+            [],
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Create the FTS5 index used by the search screen and keep it in sync with the card table.
+fn setup_search_index(connection: &Connection) -> Result<(), DbError> {
+    let index_exists = connection
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'card_search'",
+            [],
+            |row| row.get::<_, u32>(0),
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?
+        > 0;
+
+    connection
+        .execute_batch(
+            "BEGIN;
+            CREATE VIRTUAL TABLE IF NOT EXISTS card_search USING fts5(
+                name,
+                trait,
+                type,
+                details,
+                content='card',
+                content_rowid='rowid'
+            );
+            CREATE TRIGGER IF NOT EXISTS card_search_insert AFTER INSERT ON card BEGIN
+                INSERT INTO card_search (rowid, name, trait, type, details)
+                VALUES (new.rowid, new.name, new.trait, new.type, new.details);
+            END;
+            CREATE TRIGGER IF NOT EXISTS card_search_delete AFTER DELETE ON card BEGIN
+                INSERT INTO card_search (card_search, rowid, name, trait, type, details)
+                VALUES ('delete', old.rowid, old.name, old.trait, old.type, old.details);
+            END;
+            CREATE TRIGGER IF NOT EXISTS card_search_update AFTER UPDATE ON card BEGIN
+                INSERT INTO card_search (card_search, rowid, name, trait, type, details)
+                VALUES ('delete', old.rowid, old.name, old.trait, old.type, old.details);
+                INSERT INTO card_search (rowid, name, trait, type, details)
+                VALUES (new.rowid, new.name, new.trait, new.type, new.details);
+            END;
+            COMMIT;",
+        )
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    // Index the cards that were downloaded before the search index existed
+    if !index_exists {
+        connection
+            .execute(
+                "INSERT INTO card_search (card_search) VALUES ('rebuild')",
+                [],
+            )
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Fill the trait tables from the trait text of the cards downloaded before they existed
+fn setup_traits(connection: &Connection) -> Result<(), DbError> {
+    let has_traits = connection
+        .query_row("SELECT COUNT(*) FROM card_trait", [], |row| {
+            row.get::<_, u32>(0)
+        })
+        .map_err(|error| DbError::Setup(error.to_string()))?
+        > 0;
+    if has_traits {
+        return Ok(());
+    }
+
+    let mut statement = connection
+        .prepare("SELECT id, trait FROM card")
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+    let cards: Vec<(String, String)> = statement
+        .query_map([], |row| Ok((row.get("id")?, row.get("trait")?)))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Setup(error.to_string()))?;
+
+    for (card_id, card_trait) in cards {
+        save_card_traits(connection, &card_id, &parse_traits(&card_trait))
+            .map_err(|error| DbError::Setup(error.to_string()))?;
+    }
+
+    Ok(())
+}
+
+fn save_card_traits(
+    connection: &Connection,
+    card_id: &str,
+    traits: &[String],
+) -> rusqlite::Result<()> {
+    connection.execute("DELETE FROM card_trait WHERE card_id = ?", [card_id])?;
+
+    for card_trait in traits {
+        connection.execute(
+            "INSERT INTO trait (name) VALUES (?) ON CONFLICT DO NOTHING",
+            [card_trait],
+        )?;
+        connection.execute(
+            "INSERT INTO card_trait (card_id, trait_id)
+            SELECT ?1, id FROM trait WHERE name = ?2",
+            (card_id, card_trait),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn collection_card_from_row(row: &Row) -> rusqlite::Result<CollectionCard> {
+    let extension = GameExtension {
+        id: row.get_unwrap("extension_id"),
+        name: row.get_unwrap("extension_name"),
+        ..Default::default()
+    };
+    let card = Card {
+        id: row.get_unwrap("id"),
+        extension,
+        card_class: row.get_unwrap("card_class"),
+        name: row.get_unwrap("name"),
+        traits: row
+            .get::<_, Option<String>>("traits")?
+            .map(|traits| traits.split('|').map(str::to_string).collect())
+            .unwrap_or_default(),
+        rarity: row.get_unwrap("rarity"),
+        card_type: row.get_unwrap("type"),
+        hp: row.get_unwrap("hp"),
+        attack: row.get_unwrap("attack"),
+        cost: row.get_unwrap("cost"),
+        is_evolved: row.get_unwrap("is_evolved"),
+        category: row.get_unwrap("category"),
+        evolution: row.get("evolution")?,
+        details: row.get_unwrap("details"),
+    };
+    Ok(CollectionCard {
+        card,
+        is_owned: row.get_unwrap("is_owned"),
+        quantity: row.get_unwrap("quantity"),
+        wanted: row.get_unwrap("wanted"),
+        for_trade: row.get_unwrap("for_trade"),
+    })
+}
+
 fn get_extension_cards(connection: &Connection, extension: &GameExtension) -> Vec<CollectionCard> {
     let mut statement = connection
         .prepare(
@@ -57,6 +525,18 @@ fn get_extension_cards(connection: &Connection, extension: &GameExtension) -> Ve
                 *,
                 cc.is_owned,
                 cc.quantity,
+                cc.wanted,
+                cc.for_trade,
+                (
+                    SELECT GROUP_CONCAT(t.name, '|')
+                    FROM card_trait ct
+                    INNER JOIN trait t ON t.id = ct.trait_id
+                    WHERE ct.card_id = card.id
+                ) AS traits,
+                COALESCE(
+                    (SELECT base_card_id FROM card_evolution WHERE evolved_card_id = card.id),
+                    (SELECT MIN(evolved_card_id) FROM card_evolution WHERE base_card_id = card.id)
+                ) AS evolution,
                 e.id as extension_id,
                 e.name as extension_name
             FROM card
@@ -65,32 +545,7 @@ fn get_extension_cards(connection: &Connection, extension: &GameExtension) -> Ve
             WHERE e.id = ?",
         )
         .unwrap();
-    let res = statement.query_map([&extension.id], |row| {
-        let extension = GameExtension {
-            id: row.get_unwrap("extension_id"),
-            name: row.get_unwrap("extension_name"),
-        };
-        let card = Card {
-            id: row.get_unwrap("id"),
-            extension,
-            card_class: row.get_unwrap("card_class"),
-            name: row.get_unwrap("name"),
-            card_trait: row.get_unwrap("trait"),
-            rarity: row.get_unwrap("rarity"),
-            card_type: row.get_unwrap("type"),
-            hp: row.get_unwrap("hp"),
-            attack: row.get_unwrap("attack"),
-            cost: row.get_unwrap("cost"),
-            is_evolved: row.get_unwrap("is_evolved"),
-            details: row.get_unwrap("details"),
-        };
-        let collection_card = CollectionCard {
-            card,
-            is_owned: row.get_unwrap("is_owned"),
-            quantity: row.get_unwrap("quantity"),
-        };
-        Ok(collection_card)
-    });
+    let res = statement.query_map([&extension.id], collection_card_from_row);
 
     let mut collected_cards = Vec::new();
     for card in res.unwrap() {
@@ -103,16 +558,11 @@ pub fn get_extensions(config: &Config) -> Vec<ExtensionProgression> {
     let connection =
         Connection::open(config.db_file.clone()).expect("Could open the database file");
 
-    // Get the extensions from the db
+    // Get the extensions from the db, in the order of their release
     let mut statement = connection
-        .prepare("SELECT * FROM extension ORDER BY name")
+        .prepare("SELECT * FROM extension ORDER BY release_date IS NULL, release_date, id")
         .unwrap();
-    let res = statement.query_map([], |row| {
-        Ok(GameExtension {
-            id: row.get_unwrap("id"),
-            name: row.get_unwrap("name"),
-        })
-    });
+    let res = statement.query_map([], extension_from_row);
 
     // Convert the rows to a Vec<ExtensionProgression>
     let mut extensions: Vec<ExtensionProgression> = Vec::new();
@@ -136,12 +586,7 @@ pub fn get_extension(config: &Config, extension_id: &str) -> ExtensionProgressio
     let mut statement = connection
         .prepare("SELECT * FROM extension WHERE id = ?")
         .unwrap();
-    let res = statement.query_row([extension_id], |row| {
-        Ok(GameExtension {
-            id: row.get_unwrap("id"),
-            name: row.get_unwrap("name"),
-        })
-    });
+    let res = statement.query_row([extension_id], extension_from_row);
 
     let extension = res.unwrap();
     let cards = get_extension_cards(&connection, &extension);
@@ -152,45 +597,72 @@ pub fn get_extension(config: &Config, extension_id: &str) -> ExtensionProgressio
     }
 }
 
-pub fn upsert_card(config: &Config, card: Card) -> Result<(), ()> {
+fn extension_from_row(row: &Row) -> rusqlite::Result<GameExtension> {
+    Ok(GameExtension {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        set_type: row.get("set_type")?,
+        release_date: row.get("release_date")?,
+        card_count: row.get::<_, Option<u32>>("card_count")?.unwrap_or_default(),
+    })
+}
+
+/// Store a downloaded card, the release dates are the ones of the configuration used to date its
+/// extension when it is new
+pub fn upsert_card(
+    config: &Config,
+    card: Card,
+    release_dates: &[ReleaseDate],
+) -> Result<(), DbError> {
     let connection =
         Connection::open(config.db_file.clone()).expect("Could open the database file");
 
     // Create the extension if needed
     let _ = connection.execute(
         "INSERT INTO 
-            extension (id, name)
-        VALUES (?, ?)",
-        (&card.extension.id, &card.extension.name),
+            extension (id, name, set_type, release_date)
+        VALUES (?, ?, ?, ?)",
+        (
+            &card.extension.id,
+            &card.extension.name,
+            &card.extension.set_type,
+            release_date(&card.extension.id, release_dates),
+        ),
     );
 
     tracing::info!("{:?}", card);
 
     let result = connection.execute(
         "INSERT INTO 
-            card (id, name, card_class, rarity, trait, type, details, extension_id, hp, cost, attack, is_evolved)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        ON CONFLICT DO UPDATE SET details = ?7",
+            card (id, name, card_class, rarity, trait, type, details, extension_id, hp, cost, attack, is_evolved, category)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT DO UPDATE SET rarity = ?4, trait = ?5, type = ?6, details = ?7, category = ?13",
         (
             &card.id,
             &card.name,
             &card.card_class,
             &card.rarity,
-            &card.card_trait,
+            &card.traits.join(" / "),
             &card.card_type,
             &card.details,
             &card.extension.id,
             &card.hp,
             &card.cost,
             &card.attack,
-            &card.is_evolved
+            &card.is_evolved,
+            &card.category
         ),
     );
 
-    let Ok(_) = result else {
-        tracing::error!("{:?}", result.err());
-        return Err(());
-    };
+    if let Err(error) = result {
+        tracing::error!("{:?}", error);
+        return Err(DbError::Query(error.to_string()));
+    }
+
+    if let Err(error) = save_card_traits(&connection, &card.id, &card.traits) {
+        tracing::error!("{:?}", error);
+        return Err(DbError::Query(error.to_string()));
+    }
 
     // Add the card_collection
     let _ = connection.execute(
@@ -200,6 +672,58 @@ pub fn upsert_card(config: &Config, card: Card) -> Result<(), ()> {
         (&card.id, false),
     );
 
+    let _ = connection.execute(
+        "UPDATE extension SET card_count = (
+            SELECT COUNT(*)
+            FROM card
+            WHERE card.extension_id = extension.id AND card.category = 'Collectible'
+        )
+        WHERE id = ?",
+        [&card.extension.id],
+    );
+
+    Ok(())
+}
+
+/// Link each evolved card to its base follower
+pub fn link_evolutions(config: &Config) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    link_cards_evolutions(&connection).map_err(|error| DbError::Query(error.to_string()))
+}
+
+/// The base follower of an evolved card has the same name, the one of the same extension is
+/// preferred when the card has been reprinted
+fn link_cards_evolutions(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute(
+        "INSERT OR REPLACE INTO card_evolution (evolved_card_id, base_card_id)
+        SELECT evolved_card_id, base_card_id
+        FROM (
+            SELECT
+                evolved.id AS evolved_card_id,
+                COALESCE(
+                    (
+                        SELECT MIN(candidate.id)
+                        FROM card candidate
+                        WHERE candidate.is_evolved = 0
+                            AND candidate.name = evolved.name
+                            AND candidate.extension_id = evolved.extension_id
+                    ),
+                    (
+                        SELECT MIN(candidate.id)
+                        FROM card candidate
+                        WHERE candidate.is_evolved = 0
+                            AND candidate.name = evolved.name
+                    )
+                ) AS base_card_id
+            FROM card evolved
+            WHERE evolved.is_evolved = 1
+        )
+        WHERE base_card_id IS NOT NULL",
+        [],
+    )?;
+
     Ok(())
 }
 
@@ -219,14 +743,559 @@ pub fn get_all_cards_number(config: &Config) -> Vec<String> {
     cards
 }
 
-pub fn update_card_quantity(config: &Config, card_id: &str, quantity: u8) -> Result<(), ()> {
+pub fn update_card_quantity(config: &Config, card_id: &str, quantity: u8) -> Result<(), DbError> {
     let connection =
         Connection::open(config.db_file.clone()).expect("Could open the database file");
 
     let _ = connection.execute(
-        "UPDATE collected_cards SET quantity = ?1, is_owned = (CASE WHEN ?1 > 0 THEN 1 ELSE 0 END) WHERE card_id = ?2",
+        "UPDATE collected_cards SET quantity = ?1, is_owned = (CASE WHEN ?1 > 0 THEN 1 ELSE 0 END), updated_at = strftime('%s', 'now') WHERE card_id = ?2",
         (quantity, &card_id),
     );
 
     Ok(())
 }
+
+/// Own at least one copy of each of the given cards
+pub fn mark_cards_owned(config: &Config, cards_ids: &[String]) -> Result<(), DbError> {
+    let mut connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let transaction = connection
+        .transaction()
+        .map_err(|error| DbError::Query(error.to_string()))?;
+    for card_id in cards_ids {
+        transaction
+            .execute(
+                "UPDATE collected_cards SET quantity = MAX(quantity, 1), is_owned = 1, updated_at = strftime('%s', 'now') WHERE card_id = ?",
+                [card_id],
+            )
+            .map_err(|error| DbError::Query(error.to_string()))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Set the number of copies of a card the user is looking for, on top of the missing copies
+pub fn update_card_wanted(config: &Config, card_id: &str, wanted: u8) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    connection
+        .execute(
+            "UPDATE collected_cards SET wanted = ?1, updated_at = strftime('%s', 'now') WHERE card_id = ?2",
+            (wanted, card_id),
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Set the number of copies of a card the user is willing to trade
+pub fn update_card_for_trade(config: &Config, card_id: &str, for_trade: u8) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    connection
+        .execute(
+            "UPDATE collected_cards SET for_trade = ?1, updated_at = strftime('%s', 'now') WHERE card_id = ?2",
+            (for_trade, card_id),
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Statistics of the collection, the tokens and leaders are only counted when the progression
+/// settings include them
+pub fn get_collection_stats(config: &Config) -> Result<CollectionStats, DbError> {
+    let connection = Connection::open(config.db_file.clone())
+        .map_err(|error| DbError::Query(format!("{} ({})", config.db_file.display(), error)))?;
+    let include_special_cards =
+        config.progression_settings().special_cards == SpecialCardsDisplay::Included;
+
+    let mut stats = connection
+        .query_row(
+            "SELECT
+                COUNT(*) AS total_cards,
+                COALESCE(SUM(cc.is_owned), 0) AS unique_cards,
+                COALESCE(SUM(cc.quantity), 0) AS total_copies,
+                COALESCE(SUM(CASE WHEN cc.quantity > 1 THEN 1 ELSE 0 END), 0) AS duplicates
+            FROM card
+            INNER JOIN collected_cards cc ON cc.card_id = card.id
+            WHERE ?1 OR card.category = 'Collectible'",
+            [include_special_cards],
+            |row| {
+                Ok(CollectionStats {
+                    total_cards: row.get("total_cards")?,
+                    unique_cards: row.get("unique_cards")?,
+                    total_copies: row.get("total_copies")?,
+                    duplicates: row.get("duplicates")?,
+                    ..CollectionStats::default()
+                })
+            },
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    stats.by_class = category_completions(&connection, include_special_cards, "card.card_class")?;
+    stats.by_rarity = CategoryCompletion::merge_by(
+        category_completions(&connection, include_special_cards, "card.rarity")?,
+        Rarity::from,
+    );
+
+    (stats.playable_cards, stats.owned_playable_cards) = connection
+        .query_row(
+            "SELECT
+                COUNT(*) AS playable_cards,
+                COALESCE(SUM(is_owned), 0) AS owned_playable_cards
+            FROM (
+                SELECT MAX(cc.is_owned) AS is_owned
+                FROM card
+                INNER JOIN collected_cards cc ON cc.card_id = card.id
+                WHERE ?1 OR card.category = 'Collectible'
+                GROUP BY LOWER(card.name), card.is_evolved
+            )",
+            [include_special_cards],
+            |row| Ok((row.get("playable_cards")?, row.get("owned_playable_cards")?)),
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+    stats.by_type = CategoryCompletion::merge_by(
+        category_completions(&connection, include_special_cards, "card.type")?,
+        CardType::from,
+    );
+
+    let mut statement = connection
+        .prepare(
+            "SELECT
+                e.id AS extension_id,
+                e.name AS extension_name,
+                MAX(cc.updated_at) AS updated_at
+            FROM card
+            INNER JOIN extension e ON e.id = card.extension_id
+            INNER JOIN collected_cards cc ON cc.card_id = card.id
+            WHERE cc.updated_at IS NOT NULL
+            GROUP BY e.id
+            ORDER BY updated_at DESC
+            LIMIT ?",
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+    stats.recent_extensions = statement
+        .query_map([RECENT_EXTENSIONS_LIMIT], |row| {
+            Ok(RecentExtension {
+                extension: GameExtension {
+                    id: row.get("extension_id")?,
+                    name: row.get("extension_name")?,
+                    ..Default::default()
+                },
+                updated_at: row.get("updated_at")?,
+            })
+        })
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(stats)
+}
+
+/// Completion of the counted cards grouped by the given column of the card table
+fn category_completions(
+    connection: &Connection,
+    include_special_cards: bool,
+    column: &str,
+) -> Result<Vec<CategoryCompletion>, DbError> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT
+                {column} AS category,
+                COUNT(*) AS total,
+                COALESCE(SUM(cc.is_owned), 0) AS owned,
+                COALESCE(SUM(cc.quantity), 0) AS copies
+            FROM card
+            INNER JOIN collected_cards cc ON cc.card_id = card.id
+            WHERE ?1 OR card.category = 'Collectible'
+            GROUP BY {column}
+            ORDER BY {column}"
+        ))
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    statement
+        .query_map([include_special_cards], |row| {
+            Ok(CategoryCompletion {
+                name: row.get("category")?,
+                owned: row.get("owned")?,
+                total: row.get("total")?,
+                copies: row.get("copies")?,
+            })
+        })
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Query(error.to_string()))
+}
+
+/// Get the filters saved for the cards list of an extension, or empty filters if none were saved
+pub fn get_extension_filter(config: &Config, extension_id: &str) -> CardsFilter {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let filter = connection.query_row(
+        "SELECT filter FROM extension_filter WHERE extension_id = ?",
+        [extension_id],
+        |row| row.get::<_, String>("filter"),
+    );
+
+    let Ok(filter) = filter else {
+        return CardsFilter::default();
+    };
+
+    serde_yaml::from_str(&filter).unwrap_or_else(|error| {
+        tracing::warn!("Could not read the filters of {}: {}", extension_id, error);
+        CardsFilter::default()
+    })
+}
+
+pub fn save_extension_filter(
+    config: &Config,
+    extension_id: &str,
+    filter: &CardsFilter,
+) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let serialized_filter =
+        serde_yaml::to_string(filter).map_err(|error| DbError::Query(error.to_string()))?;
+
+    connection
+        .execute(
+            "INSERT INTO
+                extension_filter (extension_id, filter)
+            VALUES (?1, ?2)
+            ON CONFLICT DO UPDATE SET filter = ?2",
+            (extension_id, &serialized_filter),
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Get the sort last used on the cards table of a screen
+pub fn get_screen_sort(config: &Config, screen: &str) -> Option<CardsSort> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let sort = connection
+        .query_row(
+            "SELECT sort FROM screen_sort WHERE screen = ?",
+            [screen],
+            |row| row.get::<_, String>("sort"),
+        )
+        .ok()?;
+
+    serde_yaml::from_str(&sort)
+        .map_err(|error| tracing::warn!("Could not read the sort of {}: {}", screen, error))
+        .ok()
+}
+
+pub fn save_screen_sort(
+    config: &Config,
+    screen: &str,
+    sort: Option<&CardsSort>,
+) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let result = match sort {
+        Some(sort) => {
+            let serialized_sort =
+                serde_yaml::to_string(sort).map_err(|error| DbError::Query(error.to_string()))?;
+            connection.execute(
+                "INSERT INTO
+                    screen_sort (screen, sort)
+                VALUES (?1, ?2)
+                ON CONFLICT DO UPDATE SET sort = ?2",
+                (screen, &serialized_sort),
+            )
+        }
+        None => connection.execute("DELETE FROM screen_sort WHERE screen = ?", [screen]),
+    };
+
+    result.map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Search the cards of every extension, ranking the matches on the name first, then on the
+/// trait and type, and finally on the details of the card
+pub fn search_cards(config: &Config, search: &str) -> Result<Vec<CollectionCard>, DbError> {
+    let Some(search_query) = to_search_query(search) else {
+        return Ok(Vec::new());
+    };
+
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let mut statement = connection
+        .prepare(
+            "SELECT
+                card.*,
+                cc.is_owned,
+                cc.quantity,
+                cc.wanted,
+                cc.for_trade,
+                (
+                    SELECT GROUP_CONCAT(t.name, '|')
+                    FROM card_trait ct
+                    INNER JOIN trait t ON t.id = ct.trait_id
+                    WHERE ct.card_id = card.id
+                ) AS traits,
+                COALESCE(
+                    (SELECT base_card_id FROM card_evolution WHERE evolved_card_id = card.id),
+                    (SELECT MIN(evolved_card_id) FROM card_evolution WHERE base_card_id = card.id)
+                ) AS evolution,
+                e.id as extension_id,
+                e.name as extension_name
+            FROM card_search
+            INNER JOIN card ON card.rowid = card_search.rowid
+            INNER JOIN extension e ON e.id = card.extension_id
+            INNER JOIN collected_cards cc ON cc.card_id = card.id
+            WHERE card_search MATCH ?1
+            ORDER BY bm25(card_search, 10.0, 5.0, 5.0, 1.0), card.id
+            LIMIT ?2",
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+    let res = statement
+        .query_map(
+            (&search_query, SEARCH_RESULTS_LIMIT),
+            collection_card_from_row,
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    let mut found_cards = Vec::new();
+    for card in res {
+        found_cards.push(card.map_err(|error| DbError::Query(error.to_string()))?);
+    }
+    Ok(found_cards)
+}
+
+/// Convert the text typed by the user into a FTS5 query where every word is a prefix that must
+/// be present in the card
+fn to_search_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    Some(terms.join(" "))
+}
+
+/// Record an image of a card stored in the covers directory
+pub fn save_cached_image(
+    config: &Config,
+    card_id: &str,
+    kind: ImageKind,
+    size: u64,
+) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    connection
+        .execute(
+            "INSERT OR REPLACE INTO cached_image (card_id, kind, size, cached_at)
+            VALUES (?, ?, ?, strftime('%s', 'now'))",
+            (card_id, kind, size),
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+/// Cards and kinds of the cached images, of a single kind when one is given
+pub fn get_cached_images(
+    config: &Config,
+    kind: Option<ImageKind>,
+) -> Result<Vec<(String, ImageKind)>, DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let mut statement = connection
+        .prepare("SELECT card_id, kind FROM cached_image WHERE ?1 IS NULL OR kind = ?1")
+        .map_err(|error| DbError::Query(error.to_string()))?;
+    let images = statement
+        .query_map([kind], |row| Ok((row.get("card_id")?, row.get("kind")?)))
+        .and_then(Iterator::collect)
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(images)
+}
+
+/// Forget the cached images of the given kind, or every cached image
+pub fn clear_cached_images(config: &Config, kind: Option<ImageKind>) -> Result<(), DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    connection
+        .execute(
+            "DELETE FROM cached_image WHERE ?1 IS NULL OR kind = ?1",
+            [kind],
+        )
+        .map_err(|error| DbError::Query(error.to_string()))?;
+
+    Ok(())
+}
+
+pub fn get_image_cache_stats(config: &Config) -> Result<ImageCacheStats, DbError> {
+    let connection =
+        Connection::open(config.db_file.clone()).expect("Could open the database file");
+
+    let cache_usage = |kind: ImageKind| {
+        connection.query_row(
+            "SELECT COUNT(*) AS files, COALESCE(SUM(size), 0) AS bytes
+            FROM cached_image
+            WHERE kind = ?",
+            [kind],
+            |row| {
+                Ok(CacheUsage {
+                    files: row.get("files")?,
+                    bytes: row.get("bytes")?,
+                })
+            },
+        )
+    };
+
+    Ok(ImageCacheStats {
+        covers: cache_usage(ImageKind::Cover).map_err(|error| DbError::Query(error.to_string()))?,
+        thumbnails: cache_usage(ImageKind::Thumbnail)
+            .map_err(|error| DbError::Query(error.to_string()))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::{apply_extension_mappings, normalize_card_columns, setup_card_categories};
+    use crate::extension_mapping::ExtensionMapping;
+
+    fn extensions_connection() -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE extension (
+                    id VARCHAR(50) PRIMARY KEY,
+                    name VARCHAR(100) NOT NULL,
+                    set_type VARCHAR(50),
+                    release_date TEXT
+                );
+                CREATE TABLE card (id TEXT PRIMARY KEY, extension_id VARCHAR(50));
+                CREATE TABLE extension_filter (
+                    extension_id VARCHAR(50) PRIMARY KEY,
+                    filter TEXT NOT NULL
+                );
+                INSERT INTO extension (id, name) VALUES ('BP01', 'Advent of Genesis');
+                INSERT INTO extension (id, name) VALUES ('BSF2024', 'Promotion cards');
+                INSERT INTO extension_filter (extension_id, filter) VALUES ('BSF2024', '{}');
+                INSERT INTO card (id, extension_id) VALUES ('BP01-001', 'BP01');
+                INSERT INTO card (id, extension_id) VALUES ('BSF2024-001EN', 'BSF2024');
+                INSERT INTO card (id, extension_id) VALUES ('UNKNOWN-001', NULL);",
+            )
+            .unwrap();
+        connection
+    }
+
+    fn mappings() -> Vec<ExtensionMapping> {
+        vec![ExtensionMapping {
+            prefix: String::from("BSF"),
+            extension_id: String::from("PR"),
+        }]
+    }
+
+    fn query_strings(connection: &Connection, query: &str) -> Vec<String> {
+        let mut statement = connection.prepare(query).unwrap();
+        let rows = statement.query_map([], |row| row.get(0)).unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn mapped_cards_are_moved_to_their_extension() {
+        let connection = extensions_connection();
+
+        apply_extension_mappings(&connection, &mappings()).unwrap();
+
+        assert_eq!(
+            query_strings(
+                &connection,
+                "SELECT extension_id FROM card WHERE id = 'BSF2024-001EN'"
+            ),
+            vec!["PR"]
+        );
+        assert_eq!(
+            query_strings(&connection, "SELECT name FROM extension WHERE id = 'PR'"),
+            vec!["Promotion cards"]
+        );
+    }
+
+    #[test]
+    fn empty_extensions_are_removed_when_a_card_has_no_extension() {
+        let connection = extensions_connection();
+
+        apply_extension_mappings(&connection, &mappings()).unwrap();
+
+        assert_eq!(
+            query_strings(&connection, "SELECT id FROM extension ORDER BY id"),
+            vec!["BP01", "PR"]
+        );
+        assert!(query_strings(&connection, "SELECT extension_id FROM extension_filter").is_empty());
+    }
+
+    #[test]
+    fn saved_cards_columns_are_normalized() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE card (id TEXT PRIMARY KEY, rarity TEXT, trait TEXT, type TEXT);
+                INSERT INTO card VALUES ('BP01-001', 'LG', 'Officer/Commander', 'Follower / Evolved');
+                INSERT INTO card VALUES ('BP01-002', 'Bronze', '-', 'Spell');",
+            )
+            .unwrap();
+
+        normalize_card_columns(&connection).unwrap();
+
+        assert_eq!(
+            query_strings(&connection, "SELECT rarity FROM card ORDER BY id"),
+            vec!["Legendary", "Bronze"]
+        );
+        assert_eq!(
+            query_strings(&connection, "SELECT type FROM card ORDER BY id"),
+            vec!["Evolved Follower", "Spell"]
+        );
+        assert_eq!(
+            query_strings(&connection, "SELECT trait FROM card WHERE id = 'BP01-001'"),
+            vec!["Officer / Commander"]
+        );
+    }
+
+    #[test]
+    fn saved_cards_get_the_category_of_their_number() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE card (id TEXT PRIMARY KEY, type TEXT);
+                INSERT INTO card VALUES ('BP01-001', 'Follower');
+                INSERT INTO card VALUES ('BP01-T01EN', 'Follower');
+                INSERT INTO card VALUES ('BP01-T', 'Spell');
+                INSERT INTO card VALUES ('BP01-LD01', 'Other');
+                INSERT INTO card VALUES ('PR-001', 'Spell / Token');",
+            )
+            .unwrap();
+
+        setup_card_categories(&connection).unwrap();
+
+        assert_eq!(
+            query_strings(&connection, "SELECT category FROM card ORDER BY id"),
+            vec!["Collectible", "Leader", "Collectible", "Token", "Token"]
+        );
+    }
+}
@@ -1,4 +1,17 @@
-use std::path::PathBuf;
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// Name of the directory containing the files of the application
+pub const APPLICATION_DIRECTORY: &str = "shadowverse-collection";
+/// File placed next to the executable to enable the portable mode
+const PORTABLE_MARKER: &str = "portable";
+
+const CONFIG_FILE_VARIABLE: &str = "SHADOWVERSE_CONFIG_FILE";
+const DATA_DIRECTORY_VARIABLE: &str = "SHADOWVERSE_DATA_DIR";
+const CACHE_DIRECTORY_VARIABLE: &str = "SHADOWVERSE_CACHE_DIR";
+const PORTABLE_VARIABLE: &str = "SHADOWVERSE_PORTABLE";
 
 pub fn local_directory() -> PathBuf {
     dirs_next::data_local_dir().unwrap()
@@ -7,3 +20,123 @@ pub fn local_directory() -> PathBuf {
 pub fn config_directory() -> PathBuf {
     dirs_next::config_dir().unwrap()
 }
+
+/// Directory of the executable, used as the root of the files in portable mode
+pub fn executable_directory() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|executable| executable.parent().map(Path::to_path_buf))
+}
+
+/// Locations given on the command line or in the environment, they take precedence over the
+/// directories of the configuration file
+#[derive(Debug, Clone, Default)]
+pub struct PathOverrides {
+    pub config_file: Option<PathBuf>,
+    pub data_directory: Option<PathBuf>,
+    pub cache_directory: Option<PathBuf>,
+    pub portable: bool,
+    /// Problems found in the arguments and the environment, logged once the logger is ready
+    pub warnings: Vec<String>,
+}
+
+impl PathOverrides {
+    /// Read the `SHADOWVERSE_*` environment variables and look for the portable marker file
+    pub fn from_environment() -> Self {
+        let path = |variable: &str| {
+            std::env::var_os(variable)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        let mut warnings = Vec::new();
+        let portable_variable = match std::env::var(PORTABLE_VARIABLE) {
+            Ok(value) => !matches!(value.as_str(), "" | "0" | "false"),
+            Err(std::env::VarError::NotPresent) => false,
+            Err(std::env::VarError::NotUnicode(_)) => {
+                warnings.push(format!(
+                    "{} is not valid unicode, it is ignored",
+                    PORTABLE_VARIABLE
+                ));
+                false
+            }
+        };
+        let portable_marker = executable_directory()
+            .map(|directory| directory.join(PORTABLE_MARKER).exists())
+            .unwrap_or(false);
+
+        Self {
+            config_file: path(CONFIG_FILE_VARIABLE),
+            data_directory: path(DATA_DIRECTORY_VARIABLE),
+            cache_directory: path(CACHE_DIRECTORY_VARIABLE),
+            portable: portable_variable || portable_marker,
+            warnings,
+        }
+    }
+
+    /// Apply the `--config`, `--data-dir`, `--cache-dir` and `--portable` command line flags
+    pub fn with_args(mut self, args: impl IntoIterator<Item = OsString>) -> Self {
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let Some(arg) = arg.to_str().map(str::to_owned) else {
+                self.warnings.push(format!(
+                    "The command line argument {} is not valid unicode, it is ignored",
+                    arg.to_string_lossy()
+                ));
+                continue;
+            };
+
+            // Accept both `--flag value` and `--flag=value`
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_owned(), Some(PathBuf::from(value))),
+                None => (arg, None),
+            };
+            let target = match flag.as_str() {
+                "--config" => &mut self.config_file,
+                "--data-dir" => &mut self.data_directory,
+                "--cache-dir" => &mut self.cache_directory,
+                "--portable" => {
+                    self.portable = true;
+                    continue;
+                }
+                _ => {
+                    self.warnings
+                        .push(format!("Unknown command line argument {} ignored", flag));
+                    continue;
+                }
+            };
+
+            match value.or_else(|| args.next().map(PathBuf::from)) {
+                Some(path) => *target = Some(path),
+                None => self
+                    .warnings
+                    .push(format!("{} needs a path, it is ignored", flag)),
+            }
+        }
+
+        if self.portable && executable_directory().is_none() {
+            self.warnings.push(String::from(
+                "The directory of the executable is unknown, the portable mode is disabled",
+            ));
+        }
+
+        self
+    }
+
+    /// Directory containing the configuration file when it is not given explicitly
+    pub fn root_directory(&self) -> PathBuf {
+        match self.portable.then(executable_directory).flatten() {
+            Some(directory) => directory.join(APPLICATION_DIRECTORY),
+            None => config_directory().join(APPLICATION_DIRECTORY),
+        }
+    }
+
+    /// Directory containing the covers when no other location is configured
+    pub fn default_cache_directory(&self) -> PathBuf {
+        match self.portable.then(executable_directory).flatten() {
+            Some(directory) => directory.join(APPLICATION_DIRECTORY),
+            None => local_directory().join(APPLICATION_DIRECTORY),
+        }
+    }
+}
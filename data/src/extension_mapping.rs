@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Canonical extension of the cards whose number does not start with the id of their extension,
+/// like the promo cards numbered `BSF2023-001` that belong to the `PR` extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionMapping {
+    /// Start of the part of the card number before the `-`
+    pub prefix: String,
+    pub extension_id: String,
+}
+
+/// Mappings used when the configuration file does not define its own
+pub fn default_extension_mappings() -> Vec<ExtensionMapping> {
+    vec![ExtensionMapping {
+        prefix: String::from("BSF"),
+        extension_id: String::from("PR"),
+    }]
+}
+
+/// Id of the extension of a card from its number, the first mapping matching the number is used
+pub fn extension_id<'a>(card_number: &'a str, mappings: &'a [ExtensionMapping]) -> &'a str {
+    let prefix = card_number.split('-').next().unwrap_or_default();
+
+    mappings
+        .iter()
+        .find(|mapping| prefix.starts_with(&mapping.prefix))
+        .map(|mapping| mapping.extension_id.as_str())
+        .unwrap_or(prefix)
+}
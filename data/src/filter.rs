@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{CardClass, CardType, Rarity},
+    collection::CollectionCard,
+};
+
+/// Filters applied on the cards of an extension.
+///
+/// Every criteria is combined with the others using an AND, while the values selected inside a
+/// multi-select criteria (rarities, types, traits, ...) are combined using an OR. An empty
+/// selection does not filter anything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CardsFilter {
+    pub name: String,
+    pub card_class: Option<CardClass>,
+    pub rarities: Vec<Rarity>,
+    pub card_types: Vec<CardType>,
+    pub traits: Vec<String>,
+    pub cost: RangeFilter,
+    pub attack: RangeFilter,
+    pub hp: RangeFilter,
+    pub evolutions: Vec<EvolutionFilter>,
+    pub ownerships: Vec<OwnershipFilter>,
+}
+
+impl CardsFilter {
+    pub fn matches(&self, collection_card: &CollectionCard) -> bool {
+        let card = &collection_card.card;
+
+        let name_matches = card
+            .name()
+            .to_lowercase()
+            .contains(&self.name.to_lowercase());
+        let class_matches = self
+            .card_class
+            .as_ref()
+            .is_none_or(|card_class| &card.card_class == card_class);
+        let traits_matches = self.traits.is_empty()
+            || card
+                .traits
+                .iter()
+                .any(|card_trait| self.traits.contains(card_trait));
+        let evolution_matches = self.evolutions.is_empty()
+            || self
+                .evolutions
+                .iter()
+                .any(|evolution| evolution.matches(card.is_evolved));
+        let ownership_matches = self.ownerships.is_empty()
+            || self
+                .ownerships
+                .iter()
+                .any(|ownership| ownership.matches(collection_card));
+
+        name_matches
+            && class_matches
+            && (self.rarities.is_empty() || self.rarities.contains(&card.rarity))
+            && (self.card_types.is_empty() || self.card_types.contains(&card.card_type))
+            && traits_matches
+            && self.cost.contains(card.cost)
+            && self.attack.contains(card.attack)
+            && self.hp.contains(card.hp)
+            && evolution_matches
+            && ownership_matches
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RangeFilter {
+    pub min: Option<u8>,
+    pub max: Option<u8>,
+}
+
+impl RangeFilter {
+    pub fn contains(&self, value: u8) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvolutionFilter {
+    Base,
+    Evolved,
+}
+
+impl EvolutionFilter {
+    pub const ALL: [EvolutionFilter; 2] = [EvolutionFilter::Base, EvolutionFilter::Evolved];
+
+    fn matches(&self, is_evolved: bool) -> bool {
+        match self {
+            EvolutionFilter::Base => !is_evolved,
+            EvolutionFilter::Evolved => is_evolved,
+        }
+    }
+}
+
+impl std::fmt::Display for EvolutionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EvolutionFilter::Base => "Base",
+                EvolutionFilter::Evolved => "Evolved",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OwnershipFilter {
+    Owned,
+    Missing,
+    Duplicate,
+}
+
+impl OwnershipFilter {
+    pub const ALL: [OwnershipFilter; 3] = [
+        OwnershipFilter::Owned,
+        OwnershipFilter::Missing,
+        OwnershipFilter::Duplicate,
+    ];
+
+    fn matches(&self, collection_card: &CollectionCard) -> bool {
+        match self {
+            OwnershipFilter::Owned => collection_card.is_owned,
+            OwnershipFilter::Missing => !collection_card.is_owned,
+            OwnershipFilter::Duplicate => collection_card.quantity > 1,
+        }
+    }
+}
+
+impl std::fmt::Display for OwnershipFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OwnershipFilter::Owned => "Owned",
+                OwnershipFilter::Missing => "Missing",
+                OwnershipFilter::Duplicate => "Duplicates",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardsFilter, EvolutionFilter, OwnershipFilter, RangeFilter};
+    use crate::cards::{
+        tests::{card, collection_card},
+        Card, CardClass, Rarity,
+    };
+
+    fn gold_dragon() -> Card {
+        Card {
+            name: String::from("Forte, Dragon Emperor"),
+            card_class: CardClass::Dragoncraft,
+            rarity: Rarity::Gold,
+            traits: vec![String::from("Dragon")],
+            cost: 5,
+            ..card("BP01-040")
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_every_card() {
+        assert!(CardsFilter::default().is_empty());
+        assert!(CardsFilter::default().matches(&collection_card(gold_dragon(), 0)));
+    }
+
+    #[test]
+    fn criteria_are_combined_with_and() {
+        let filter = CardsFilter {
+            name: String::from("dragon"),
+            card_class: Some(CardClass::Dragoncraft),
+            ..Default::default()
+        };
+        assert!(filter.matches(&collection_card(gold_dragon(), 0)));
+
+        let filter = CardsFilter {
+            card_class: Some(CardClass::Forestcraft),
+            ..filter
+        };
+        assert!(!filter.matches(&collection_card(gold_dragon(), 0)));
+    }
+
+    #[test]
+    fn selected_values_are_combined_with_or() {
+        let filter = CardsFilter {
+            rarities: vec![Rarity::Bronze, Rarity::Gold],
+            traits: vec![String::from("Officer"), String::from("Dragon")],
+            ..Default::default()
+        };
+        assert!(filter.matches(&collection_card(gold_dragon(), 0)));
+
+        let filter = CardsFilter {
+            rarities: vec![Rarity::Legendary],
+            ..filter
+        };
+        assert!(!filter.matches(&collection_card(gold_dragon(), 0)));
+    }
+
+    #[test]
+    fn ranges_include_their_bounds() {
+        let range = RangeFilter {
+            min: Some(2),
+            max: Some(5),
+        };
+
+        assert!(range.contains(2));
+        assert!(range.contains(5));
+        assert!(!range.contains(1));
+        assert!(!range.contains(6));
+        assert!(RangeFilter::default().contains(u8::MAX));
+    }
+
+    #[test]
+    fn evolution_and_ownership_filters() {
+        let evolved = collection_card(
+            Card {
+                is_evolved: true,
+                ..gold_dragon()
+            },
+            2,
+        );
+        let filter = |evolutions, ownerships| CardsFilter {
+            evolutions,
+            ownerships,
+            ..Default::default()
+        };
+
+        assert!(filter(vec![EvolutionFilter::Evolved], vec![]).matches(&evolved));
+        assert!(!filter(vec![EvolutionFilter::Base], vec![]).matches(&evolved));
+        assert!(filter(vec![], vec![OwnershipFilter::Duplicate]).matches(&evolved));
+        assert!(!filter(vec![], vec![OwnershipFilter::Missing]).matches(&evolved));
+        assert!(filter(vec![], vec![OwnershipFilter::Missing])
+            .matches(&collection_card(gold_dragon(), 0)));
+    }
+}
@@ -1,5 +1,14 @@
 pub mod cards;
 pub mod collection;
 pub mod config;
+pub mod covers;
 pub mod db;
 pub mod environment;
+pub mod extension_mapping;
+pub mod filter;
+pub mod printing;
+pub mod release_date;
+pub mod sort;
+pub mod stats;
+pub mod themes;
+pub mod trade;
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use crate::collection::{CollectionCard, PlaysetTarget};
+
+/// Every printing of a card: the regular one and its alternate versions (SL, UR, SP, ...)
+#[derive(Debug, Clone)]
+pub struct CardPrintings<'a> {
+    pub printings: Vec<&'a CollectionCard>,
+}
+
+impl<'a> CardPrintings<'a> {
+    /// Copies that can be put in a deck, whatever their printing
+    pub fn playable_copies(&self) -> u32 {
+        self.printings
+            .iter()
+            .map(|printing| printing.quantity as u32)
+            .sum()
+    }
+
+    /// Position of the printing holding the playset, the regular one when it exists
+    fn main_printing(&self) -> usize {
+        self.printings
+            .iter()
+            .position(|printing| !printing.card.rarity.is_variant())
+            .unwrap_or_default()
+    }
+
+    /// Share of the playset of each printing: the copies of every printing count toward the
+    /// playset of the main printing, and the copies above the playset are taken from the regular
+    /// printing before the alternate ones
+    pub fn playset_shares(
+        &self,
+        target: &PlaysetTarget,
+    ) -> Vec<(&'a CollectionCard, PlaysetShare)> {
+        if self.printings.is_empty() {
+            return Vec::new();
+        }
+        let mut shares = vec![PlaysetShare::default(); self.printings.len()];
+
+        let main_printing = self.main_printing();
+        let playset = target.for_card(&self.printings[main_printing].card);
+        let playable_copies = self.playable_copies().min(u8::MAX as u32) as u8;
+        shares[main_printing].target = playset;
+        shares[main_printing].owned = playable_copies.min(playset);
+
+        let mut extra_copies = playable_copies.saturating_sub(playset);
+        let trade_order = std::iter::once(main_printing)
+            .chain((0..self.printings.len()).filter(|position| *position != main_printing));
+        for position in trade_order {
+            let copies = extra_copies.min(self.printings[position].quantity);
+            shares[position].extra = copies;
+            extra_copies -= copies;
+        }
+
+        self.printings.iter().copied().zip(shares).collect()
+    }
+}
+
+/// Part of the playset of a card held by one of its printings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlaysetShare {
+    /// Copies to collect, zero for the alternate printings of a card
+    pub target: u8,
+    /// Copies of all the printings counted toward the target
+    pub owned: u8,
+    /// Copies of this printing above the playset
+    pub extra: u8,
+}
+
+impl PlaysetShare {
+    /// Copies still needed to complete the playset
+    pub fn missing_copies(&self) -> u8 {
+        self.target.saturating_sub(self.owned)
+    }
+}
+
+/// Group the cards printed under several numbers, keeping the order of their first printing
+pub fn group_printings<'a>(
+    cards: impl IntoIterator<Item = &'a CollectionCard>,
+) -> Vec<CardPrintings<'a>> {
+    let mut positions: HashMap<(String, bool), usize> = HashMap::new();
+    let mut groups: Vec<CardPrintings> = Vec::new();
+
+    for collection_card in cards {
+        let position = *positions
+            .entry(collection_card.card.play_identity())
+            .or_insert_with(|| {
+                groups.push(CardPrintings {
+                    printings: Vec::new(),
+                });
+                groups.len() - 1
+            });
+        groups[position].printings.push(collection_card);
+    }
+
+    groups
+}
+
+/// Number of copies playable for each card number, counting the copies of all its printings
+pub fn playable_copies(cards: &[CollectionCard]) -> HashMap<String, u32> {
+    group_printings(cards)
+        .iter()
+        .flat_map(|group| {
+            let playable_copies = group.playable_copies();
+            group
+                .printings
+                .iter()
+                .map(move |printing| (printing.card.id.clone(), playable_copies))
+        })
+        .collect()
+}
+
+/// Share of the playset held by each card number, computed over all the printings of the cards
+pub fn playset_shares<'a>(
+    cards: impl IntoIterator<Item = &'a CollectionCard>,
+    target: &PlaysetTarget,
+) -> HashMap<String, PlaysetShare> {
+    group_printings(cards)
+        .iter()
+        .flat_map(|group| group.playset_shares(target))
+        .map(|(printing, share)| (printing.card.id.clone(), share))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{playset_shares, PlaysetShare};
+    use crate::{
+        cards::{
+            tests::{card, collection_card},
+            Card, Rarity,
+        },
+        collection::PlaysetTarget,
+    };
+
+    fn printing(id: &str, rarity: Rarity) -> Card {
+        Card {
+            name: String::from("Shadow Reaper"),
+            rarity,
+            ..card(id)
+        }
+    }
+
+    #[test]
+    fn alternate_printings_count_toward_the_playset_of_the_regular_printing() {
+        let cards = vec![
+            collection_card(printing("BP01-SL01", Rarity::SuperLegendary), 1),
+            collection_card(printing("BP01-001", Rarity::Legendary), 1),
+        ];
+
+        let shares = playset_shares(&cards, &PlaysetTarget::default());
+
+        assert_eq!(
+            shares["BP01-001"],
+            PlaysetShare {
+                target: 3,
+                owned: 2,
+                extra: 0
+            }
+        );
+        assert_eq!(shares["BP01-001"].missing_copies(), 1);
+        assert_eq!(shares["BP01-SL01"], PlaysetShare::default());
+    }
+
+    #[test]
+    fn regular_copies_are_traded_before_the_alternate_printings() {
+        let cards = vec![
+            collection_card(printing("BP01-SL01", Rarity::SuperLegendary), 2),
+            collection_card(printing("BP01-001", Rarity::Legendary), 3),
+        ];
+
+        let shares = playset_shares(&cards, &PlaysetTarget::default());
+
+        assert_eq!(shares["BP01-001"].extra, 2);
+        assert_eq!(shares["BP01-001"].missing_copies(), 0);
+        assert_eq!(shares["BP01-SL01"].extra, 0);
+    }
+}
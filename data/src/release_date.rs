@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// Release date of an extension, the website of the cards does not give it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseDate {
+    pub extension_id: String,
+    /// Day of the release, as `YYYY-MM-DD`
+    pub date: String,
+}
+
+impl ReleaseDate {
+    pub fn is_valid(&self) -> bool {
+        let parts: Vec<&str> = self.date.split('-').collect();
+
+        matches!(parts[..], [year, month, day]
+            if year.len() == 4 && month.len() == 2 && day.len() == 2
+                && parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())))
+    }
+}
+
+/// Release date of the extension, unknown when no date is listed for it
+pub fn release_date<'a>(extension_id: &str, release_dates: &'a [ReleaseDate]) -> Option<&'a str> {
+    release_dates
+        .iter()
+        .find(|release_date| release_date.extension_id == extension_id)
+        .map(|release_date| release_date.date.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{release_date, ReleaseDate};
+
+    fn release(date: &str) -> ReleaseDate {
+        ReleaseDate {
+            extension_id: String::from("BP01"),
+            date: date.to_string(),
+        }
+    }
+
+    #[test]
+    fn dates_must_be_written_as_year_month_day() {
+        assert!(release("2024-06-28").is_valid());
+        assert!(!release("28/06/2024").is_valid());
+        assert!(!release("2024-6-28").is_valid());
+        assert!(!release("").is_valid());
+    }
+
+    #[test]
+    fn unlisted_extensions_have_no_date() {
+        let release_dates = vec![release("2024-06-28")];
+
+        assert_eq!(release_date("BP01", &release_dates), Some("2024-06-28"));
+        assert_eq!(release_date("BP02", &release_dates), None);
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collection::{CollectionCard, ExtensionProgression};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortField {
+    Number,
+    Name,
+    Rarity,
+    Class,
+    Type,
+    Cost,
+    Extension,
+    Quantity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardsSort {
+    pub field: SortField,
+    pub order: SortOrder,
+}
+
+impl CardsSort {
+    /// Sort to apply when the user clicks on the column of the given field: a new field is
+    /// sorted ascending, then descending, and the third click removes the sort
+    pub fn next(current: Option<CardsSort>, field: SortField) -> Option<CardsSort> {
+        match current {
+            Some(CardsSort {
+                field: current_field,
+                order: SortOrder::Ascending,
+            }) if current_field == field => Some(CardsSort {
+                field,
+                order: SortOrder::Descending,
+            }),
+            Some(CardsSort {
+                field: current_field,
+                order: SortOrder::Descending,
+            }) if current_field == field => None,
+            _ => Some(CardsSort {
+                field,
+                order: SortOrder::Ascending,
+            }),
+        }
+    }
+
+    /// Sort the cards, the card number is used to break the ties so the order stays stable
+    pub fn sort(&self, cards: &mut [CollectionCard]) {
+        cards.sort_by(|first, second| {
+            let ordering = self
+                .compare(first, second)
+                .then_with(|| first.card.id.cmp(&second.card.id));
+
+            match self.order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    fn compare(&self, first: &CollectionCard, second: &CollectionCard) -> Ordering {
+        match self.field {
+            SortField::Number => first.card.id.cmp(&second.card.id),
+            SortField::Name => first
+                .card
+                .name()
+                .to_lowercase()
+                .cmp(&second.card.name().to_lowercase()),
+            SortField::Rarity => first.card.rarity.cmp(&second.card.rarity),
+            SortField::Class => first.card.card_class.cmp(&second.card.card_class),
+            SortField::Type => first.card.card_type.cmp(&second.card.card_type),
+            SortField::Cost => first.card.cost.cmp(&second.card.cost),
+            SortField::Extension => first.card.extension.name.cmp(&second.card.extension.name),
+            SortField::Quantity => first.quantity.cmp(&second.quantity),
+        }
+    }
+}
+
+/// Order of the extensions in the extensions list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtensionsSort {
+    #[default]
+    ReleaseDate,
+    Name,
+    SetType,
+    CardCount,
+}
+
+impl ExtensionsSort {
+    pub const ALL: [ExtensionsSort; 4] = [
+        ExtensionsSort::ReleaseDate,
+        ExtensionsSort::Name,
+        ExtensionsSort::SetType,
+        ExtensionsSort::CardCount,
+    ];
+
+    /// Sort the extensions, the ones with an unknown release date are put after the others and
+    /// the extension id is used to break the ties
+    pub fn sort(&self, extensions: &mut [ExtensionProgression]) {
+        extensions.sort_by(|first, second| {
+            let (first, second) = (&first.extension, &second.extension);
+            let ordering = match self {
+                ExtensionsSort::ReleaseDate => first
+                    .release_date
+                    .is_none()
+                    .cmp(&second.release_date.is_none())
+                    .then_with(|| first.release_date.cmp(&second.release_date)),
+                ExtensionsSort::Name => first.name.to_lowercase().cmp(&second.name.to_lowercase()),
+                ExtensionsSort::SetType => first.set_type.cmp(&second.set_type),
+                ExtensionsSort::CardCount => second.card_count.cmp(&first.card_count),
+            };
+
+            ordering.then_with(|| first.id.cmp(&second.id))
+        });
+    }
+}
+
+impl std::fmt::Display for ExtensionsSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExtensionsSort::ReleaseDate => "Release date",
+                ExtensionsSort::Name => "Name",
+                ExtensionsSort::SetType => "Set type",
+                ExtensionsSort::CardCount => "Most cards",
+            }
+        )
+    }
+}
+
+/// How the extensions list is displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtensionsListSettings {
+    pub sort: ExtensionsSort,
+    pub group_by_set_type: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardsSort, ExtensionsSort, SortField, SortOrder};
+    use crate::{
+        cards::{
+            tests::{card, collection_card},
+            Card, GameExtension, Rarity,
+        },
+        collection::{CollectionCard, ExtensionProgression},
+    };
+
+    fn cards_ids(cards: &[CollectionCard]) -> Vec<&str> {
+        cards
+            .iter()
+            .map(|collection_card| collection_card.card.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn clicking_a_column_cycles_through_the_orders() {
+        let ascending = CardsSort::next(None, SortField::Cost);
+        assert_eq!(
+            ascending,
+            Some(CardsSort {
+                field: SortField::Cost,
+                order: SortOrder::Ascending
+            })
+        );
+
+        let descending = CardsSort::next(ascending, SortField::Cost);
+        assert_eq!(
+            descending.map(|sort| sort.order),
+            Some(SortOrder::Descending)
+        );
+        assert_eq!(CardsSort::next(descending, SortField::Cost), None);
+        assert_eq!(
+            CardsSort::next(descending, SortField::Name).map(|sort| sort.order),
+            Some(SortOrder::Ascending)
+        );
+    }
+
+    #[test]
+    fn ties_are_broken_by_the_card_number() {
+        let rarity = |id: &str, rarity: Rarity| collection_card(Card { rarity, ..card(id) }, 0);
+        let mut cards = vec![
+            rarity("BP01-003", Rarity::Gold),
+            rarity("BP01-002", Rarity::Bronze),
+            rarity("BP01-001", Rarity::Gold),
+        ];
+
+        CardsSort {
+            field: SortField::Rarity,
+            order: SortOrder::Ascending,
+        }
+        .sort(&mut cards);
+        assert_eq!(cards_ids(&cards), vec!["BP01-002", "BP01-001", "BP01-003"]);
+
+        CardsSort {
+            field: SortField::Rarity,
+            order: SortOrder::Descending,
+        }
+        .sort(&mut cards);
+        assert_eq!(cards_ids(&cards), vec!["BP01-003", "BP01-001", "BP01-002"]);
+    }
+
+    #[test]
+    fn extensions_without_release_date_are_last() {
+        let extension = |id: &str, release_date: Option<&str>| ExtensionProgression {
+            extension: GameExtension {
+                id: id.to_string(),
+                release_date: release_date.map(str::to_string),
+                ..Default::default()
+            },
+            extension_cards: Vec::new(),
+        };
+        let mut extensions = vec![
+            extension("PR", None),
+            extension("BP02", Some("2024-09-01")),
+            extension("BP01", Some("2024-06-01")),
+            extension("ECP01", None),
+        ];
+
+        ExtensionsSort::ReleaseDate.sort(&mut extensions);
+
+        let ids: Vec<&str> = extensions
+            .iter()
+            .map(|progression| progression.extension.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["BP01", "BP02", "ECP01", "PR"]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::cards::{GameExtension, Rarity};
+
+/// Summary of the whole collection
+#[derive(Debug, Clone, Default)]
+pub struct CollectionStats {
+    pub total_cards: u32,
+    pub unique_cards: u32,
+    pub total_copies: u32,
+    /// Cards owned more than once
+    pub duplicates: u32,
+    /// Distinct cards to play with, the alternate printings of a card are counted once
+    pub playable_cards: u32,
+    pub owned_playable_cards: u32,
+    pub by_class: Vec<CategoryCompletion>,
+    pub by_rarity: Vec<CategoryCompletion>,
+    pub by_type: Vec<CategoryCompletion>,
+    pub recent_extensions: Vec<RecentExtension>,
+}
+
+impl CollectionStats {
+    /// Owned and total number of alternate printings (SL, UR, SP, ...)
+    pub fn collectible_variants(&self) -> (u32, u32) {
+        self.by_rarity
+            .iter()
+            .filter(|completion| Rarity::from(completion.name.clone()).is_variant())
+            .fold((0, 0), |(owned, total), completion| {
+                (owned + completion.owned, total + completion.total)
+            })
+    }
+}
+
+/// Completion of the cards sharing the same class, rarity or type
+#[derive(Debug, Clone)]
+pub struct CategoryCompletion {
+    pub name: String,
+    pub owned: u32,
+    pub total: u32,
+    pub copies: u32,
+}
+
+impl CategoryCompletion {
+    /// Merge the completions whose names designate the same category, like the raw types saved by
+    /// the previous versions, and sort them in the order of the category
+    pub fn merge_by<K: Ord + Display>(
+        completions: Vec<CategoryCompletion>,
+        category: impl Fn(String) -> K,
+    ) -> Vec<CategoryCompletion> {
+        let mut merged: BTreeMap<K, CategoryCompletion> = BTreeMap::new();
+
+        for completion in completions {
+            let key = category(completion.name.clone());
+            match merged.get_mut(&key) {
+                Some(merged) => {
+                    merged.owned += completion.owned;
+                    merged.total += completion.total;
+                    merged.copies += completion.copies;
+                }
+                None => {
+                    merged.insert(
+                        key,
+                        CategoryCompletion {
+                            name: String::new(),
+                            ..completion
+                        },
+                    );
+                }
+            }
+        }
+
+        merged
+            .into_iter()
+            .map(|(key, completion)| CategoryCompletion {
+                name: key.to_string(),
+                ..completion
+            })
+            .collect()
+    }
+
+    pub fn progression(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.owned as f32 / self.total as f32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentExtension {
+    pub extension: GameExtension,
+    /// Unix timestamp of the last change made to a card of the extension
+    pub updated_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CategoryCompletion;
+    use crate::cards::CardType;
+
+    fn completion(name: &str, owned: u32, total: u32) -> CategoryCompletion {
+        CategoryCompletion {
+            name: name.to_string(),
+            owned,
+            total,
+            copies: owned,
+        }
+    }
+
+    #[test]
+    fn raw_types_are_merged_in_their_category() {
+        let merged = CategoryCompletion::merge_by(
+            vec![
+                completion("Spell", 1, 2),
+                completion("Follower / Evolved", 1, 3),
+                completion("Evolved Follower", 2, 4),
+                completion("Follower", 0, 5),
+            ],
+            CardType::from,
+        );
+
+        let merged: Vec<_> = merged
+            .iter()
+            .map(|completion| (completion.name.as_str(), completion.owned, completion.total))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![
+                ("Follower", 0, 5),
+                ("Evolved Follower", 3, 7),
+                ("Spell", 1, 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::config::Config;
+
+const THEMES_DIRECTORY: &str = "themes";
+
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("Could not read the theme {0}: {1}")]
+    Read(String, String),
+    #[error("Invalid theme {0}: {1}")]
+    Parse(String, String),
+}
+
+/// Theme written by the user in a YAML or TOML file of the themes directory
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeDefinition {
+    /// Name used in the configuration, the name of the file when it is not set
+    pub name: String,
+    /// Theme the missing colors are taken from
+    pub base: Option<String>,
+    pub colors: ThemeColors,
+}
+
+/// Colors of a theme, written as `#rrggbb`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeColors {
+    pub primary: Option<String>,
+    pub dark_primary: Option<String>,
+    pub light_primary: Option<String>,
+    pub accent: Option<String>,
+    pub background: Option<String>,
+    pub background_light: Option<String>,
+    pub primary_text: Option<String>,
+    pub secondary_text: Option<String>,
+}
+
+impl ThemeColors {
+    const KEYS: [&'static str; 8] = [
+        "primary",
+        "dark_primary",
+        "light_primary",
+        "accent",
+        "background",
+        "background_light",
+        "primary_text",
+        "secondary_text",
+    ];
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Option<String>> {
+        match key {
+            "primary" => Some(&mut self.primary),
+            "dark_primary" => Some(&mut self.dark_primary),
+            "light_primary" => Some(&mut self.light_primary),
+            "accent" => Some(&mut self.accent),
+            "background" => Some(&mut self.background),
+            "background_light" => Some(&mut self.background_light),
+            "primary_text" => Some(&mut self.primary_text),
+            "secondary_text" => Some(&mut self.secondary_text),
+            _ => None,
+        }
+    }
+
+    fn values(&self) -> [&Option<String>; 8] {
+        [
+            &self.primary,
+            &self.dark_primary,
+            &self.light_primary,
+            &self.accent,
+            &self.background,
+            &self.background_light,
+            &self.primary_text,
+            &self.secondary_text,
+        ]
+    }
+}
+
+/// Directory containing the themes of the user, next to the configuration file
+pub fn themes_directory(config: &Config) -> PathBuf {
+    config
+        .config_file
+        .parent()
+        .map(|directory| directory.join(THEMES_DIRECTORY))
+        .unwrap_or_else(|| PathBuf::from(THEMES_DIRECTORY))
+}
+
+/// Load every theme of the themes directory, the invalid files are skipped
+pub fn load_user_themes(config: &Config) -> Vec<ThemeDefinition> {
+    let Ok(entries) = std::fs::read_dir(themes_directory(config)) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|extension| extension.to_str()),
+                Some("yaml" | "yml" | "toml")
+            )
+        })
+        .filter_map(|path| match load_theme(path) {
+            Ok(theme) => Some(theme),
+            Err(error) => {
+                tracing::warn!("{}", error);
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn load_theme(path: &Path) -> Result<ThemeDefinition, ThemeError> {
+    let file_name = path.display().to_string();
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| ThemeError::Read(file_name.clone(), error.to_string()))?;
+
+    let mut theme = if path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        parse_toml(&content).map_err(|error| ThemeError::Parse(file_name.clone(), error))?
+    } else if content.trim().is_empty() {
+        ThemeDefinition::default()
+    } else {
+        serde_yaml::from_str(&content)
+            .map_err(|error| ThemeError::Parse(file_name.clone(), error.to_string()))?
+    };
+
+    if theme.name.trim().is_empty() {
+        theme.name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+
+    if let Some(color) = theme
+        .colors
+        .values()
+        .into_iter()
+        .flatten()
+        .find(|color| parse_color(color).is_none())
+    {
+        return Err(ThemeError::Parse(
+            file_name,
+            format!("{} is not a #rrggbb color", color),
+        ));
+    }
+
+    Ok(theme)
+}
+
+/// Red, green and blue components of a `#rrggbb` color
+pub fn parse_color(color: &str) -> Option<[u8; 3]> {
+    let hex = color.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let component = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+    Some([component(0)?, component(2)?, component(4)?])
+}
+
+fn parse_toml(content: &str) -> Result<ThemeDefinition, String> {
+    let document = content
+        .parse::<toml_edit::Document>()
+        .map_err(|error| error.to_string())?;
+    let string = |item: &toml_edit::Item, key: &str| {
+        item.as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| format!("{} must be a string", key))
+    };
+
+    let mut theme = ThemeDefinition::default();
+    for (key, item) in document.iter() {
+        match key {
+            "name" => theme.name = string(item, key)?,
+            "base" => theme.base = Some(string(item, key)?),
+            "colors" => {
+                let colors = item
+                    .as_table_like()
+                    .ok_or_else(|| String::from("colors must be a table"))?;
+                for (color_key, color) in colors.iter() {
+                    let Some(value) = theme.colors.get_mut(color_key) else {
+                        return Err(format!(
+                            "unknown color {}, the colors are {}",
+                            color_key,
+                            ThemeColors::KEYS.join(", ")
+                        ));
+                    };
+                    *value = Some(string(color, color_key)?);
+                }
+            }
+            _ => return Err(format!("unknown key {}", key)),
+        }
+    }
+
+    Ok(theme)
+}
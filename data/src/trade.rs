@@ -0,0 +1,453 @@
+use std::collections::{BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use crate::{
+    cards::{Card, GameExtension, Rarity},
+    collection::{
+        share_of, CollectionCard, ExtensionProgression, PlaysetTarget, ProgressionSettings,
+    },
+    printing::playset_shares,
+};
+
+#[derive(Debug, Clone)]
+pub struct TradeEntry {
+    pub card: Card,
+    pub copies: u8,
+}
+
+/// Cards the user is looking for and cards the user can give in a trade
+#[derive(Debug, Clone, Default)]
+pub struct TradeList {
+    pub wants: Vec<TradeEntry>,
+    pub tradeables: Vec<TradeEntry>,
+}
+
+impl TradeList {
+    /// Trade list of the counted cards of the extensions, the tokens and leaders are only listed
+    /// when they are counted in the progression
+    pub fn from_collection(
+        progressions: &[ExtensionProgression],
+        settings: &ProgressionSettings,
+    ) -> Self {
+        let mut trade_list = Self::default();
+        let counted_cards = || {
+            progressions
+                .iter()
+                .flat_map(|progression| progression.counted_cards(settings.special_cards))
+        };
+        let shares = playset_shares(counted_cards(), &settings.target);
+
+        for collection_card in counted_cards() {
+            let share = share_of(&shares, collection_card);
+            let wanted_copies = collection_card.wanted_copies(&share);
+            if wanted_copies > 0 {
+                trade_list.wants.push(TradeEntry {
+                    card: collection_card.card.clone(),
+                    copies: wanted_copies,
+                });
+            }
+
+            let tradeable_copies = collection_card.tradeable_copies(&share);
+            if tradeable_copies > 0 {
+                trade_list.tradeables.push(TradeEntry {
+                    card: collection_card.card.clone(),
+                    copies: tradeable_copies,
+                });
+            }
+        }
+
+        trade_list
+    }
+
+    /// Text version of the list, grouped by extension, to post it in the trading groups
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for (title, entries) in [("Wants", &self.wants), ("Trades", &self.tradeables)] {
+            text.push_str(&format!("== {} ==\n", title));
+            for (extension, entries) in group_by_extension(entries) {
+                text.push_str(&format!("[{}] {}\n", extension.id, extension.name));
+                for entry in entries {
+                    text.push_str(&format!(
+                        "{}x {} {}\n",
+                        entry.copies,
+                        entry.card.id,
+                        entry.card.name()
+                    ));
+                }
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+/// Group the entries by extension, keeping the order in which the extensions first appear
+pub fn group_by_extension(entries: &[TradeEntry]) -> Vec<(&GameExtension, Vec<&TradeEntry>)> {
+    let mut groups: Vec<(&GameExtension, Vec<&TradeEntry>)> = Vec::new();
+
+    for entry in entries {
+        match groups
+            .iter_mut()
+            .find(|(extension, _)| extension.id == entry.card.extension.id)
+        {
+            Some((_, group)) => group.push(entry),
+            None => groups.push((&entry.card.extension, vec![entry])),
+        }
+    }
+
+    groups
+}
+
+/// First line of the files created by [`collection_to_text`]
+const COLLECTION_HEADER: &str = "# shadowverse-collection";
+
+#[derive(Debug, Error)]
+pub enum TradeError {
+    #[error("Line {0} is not a valid card line: {1}")]
+    InvalidLine(usize, String),
+    #[error("The file does not contain any known card")]
+    Empty,
+}
+
+/// Cards to exchange with a friend
+#[derive(Debug, Clone, Default)]
+pub struct ProposedTrade {
+    pub given: Vec<TradeEntry>,
+    pub received: Vec<TradeEntry>,
+}
+
+/// Propose a trade between two collections: the copies one side can trade and the other side
+/// wants are exchanged, keeping the same number of copies of each rarity on both sides
+pub fn propose_trade(
+    ours: &[CollectionCard],
+    theirs: &[CollectionCard],
+    settings: &ProgressionSettings,
+) -> ProposedTrade {
+    let given = matching_copies(ours, theirs, settings);
+    let received = matching_copies(theirs, ours, settings);
+
+    let rarities: BTreeSet<&Rarity> = given
+        .iter()
+        .chain(&received)
+        .map(|entry| &entry.card.rarity)
+        .collect();
+
+    let mut proposed_trade = ProposedTrade::default();
+    for rarity in rarities {
+        let given = entries_of_rarity(&given, rarity);
+        let received = entries_of_rarity(&received, rarity);
+        let copies = copies_count(&given).min(copies_count(&received));
+
+        proposed_trade.given.extend(take_copies(given, copies));
+        proposed_trade
+            .received
+            .extend(take_copies(received, copies));
+    }
+
+    proposed_trade
+}
+
+/// Copies of the counted cards the `giver` can trade that the `receiver` wants, sorted by card
+/// number
+fn matching_copies<'a>(
+    giver: &'a [CollectionCard],
+    receiver: &'a [CollectionCard],
+    settings: &ProgressionSettings,
+) -> Vec<TradeEntry> {
+    let counted_cards = |cards: &'a [CollectionCard]| {
+        cards
+            .iter()
+            .filter(|collection_card| settings.special_cards.counts(&collection_card.card))
+    };
+    let giver_shares = playset_shares(counted_cards(giver), &settings.target);
+    let receiver_shares = playset_shares(counted_cards(receiver), &settings.target);
+
+    let wanted_copies: HashMap<&str, u8> = counted_cards(receiver)
+        .map(|collection_card| {
+            (
+                collection_card.card.id.as_str(),
+                collection_card.wanted_copies(&share_of(&receiver_shares, collection_card)),
+            )
+        })
+        .collect();
+
+    let mut entries: Vec<TradeEntry> = counted_cards(giver)
+        .filter_map(|collection_card| {
+            let wanted = wanted_copies.get(collection_card.card.id.as_str())?;
+            let share = share_of(&giver_shares, collection_card);
+            let copies = collection_card.tradeable_copies(&share).min(*wanted);
+            (copies > 0).then(|| TradeEntry {
+                card: collection_card.card.clone(),
+                copies,
+            })
+        })
+        .collect();
+    entries.sort_by(|first, second| first.card.id.cmp(&second.card.id));
+
+    entries
+}
+
+fn entries_of_rarity(entries: &[TradeEntry], rarity: &Rarity) -> Vec<TradeEntry> {
+    entries
+        .iter()
+        .filter(|entry| &entry.card.rarity == rarity)
+        .cloned()
+        .collect()
+}
+
+fn copies_count(entries: &[TradeEntry]) -> u32 {
+    entries.iter().map(|entry| entry.copies as u32).sum()
+}
+
+/// Keep the first entries until the given number of copies is reached
+fn take_copies(entries: Vec<TradeEntry>, mut copies: u32) -> Vec<TradeEntry> {
+    let mut taken = Vec::new();
+    for mut entry in entries {
+        if copies == 0 {
+            break;
+        }
+
+        entry.copies = entry.copies.min(copies.min(u8::MAX as u32) as u8);
+        copies -= entry.copies as u32;
+        taken.push(entry);
+    }
+
+    taken
+}
+
+/// Export the whole collection so a friend can compute a trade with it
+pub fn collection_to_text(cards: &[CollectionCard]) -> String {
+    let mut text = format!("{}\n", COLLECTION_HEADER);
+    for collection_card in cards {
+        text.push_str(&format!(
+            "{};{};{};{}\n",
+            collection_card.card.id,
+            collection_card.quantity,
+            collection_card.wanted,
+            collection_card.for_trade
+        ));
+    }
+
+    text
+}
+
+/// Read the collection of a friend, exported with [`collection_to_text`] or as a trade list with
+/// [`TradeList::to_text`]. The cards are looked up in our own collection, the unknown ones are
+/// ignored.
+pub fn parse_friend_collection(
+    text: &str,
+    known_cards: &[CollectionCard],
+    target: &PlaysetTarget,
+) -> Result<Vec<CollectionCard>, TradeError> {
+    let known_cards: HashMap<&str, &Card> = known_cards
+        .iter()
+        .map(|collection_card| (collection_card.card.id.as_str(), &collection_card.card))
+        .collect();
+
+    let mut lines = text.lines().enumerate().peekable();
+    let is_collection = lines
+        .peek()
+        .is_some_and(|(_, line)| line.trim() == COLLECTION_HEADER);
+
+    let mut friend_cards: Vec<CollectionCard> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut is_wants_section = true;
+    for (index, line) in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        if line.starts_with("==") {
+            is_wants_section = line.contains("Wants");
+            continue;
+        }
+
+        let invalid_line = || TradeError::InvalidLine(index + 1, line.to_string());
+        let (card_id, quantity, wanted, for_trade) = if is_collection {
+            let fields: Vec<&str> = line.split(';').collect();
+            let [card_id, quantity, wanted, for_trade] = fields[..] else {
+                return Err(invalid_line());
+            };
+            let parse = |field: &str| field.trim().parse::<u8>().map_err(|_| invalid_line());
+
+            (card_id, parse(quantity)?, parse(wanted)?, parse(for_trade)?)
+        } else {
+            // A trade list only tells what is missing or extra, so the playset is assumed
+            // to be owned and the listed copies are added on top of it
+            let (copies, rest) = line.split_once("x ").ok_or_else(invalid_line)?;
+            let copies = copies.trim().parse::<u8>().map_err(|_| invalid_line())?;
+            let card_id = rest.split_whitespace().next().ok_or_else(invalid_line)?;
+            let Some(card) = known_cards.get(card_id) else {
+                continue;
+            };
+            let playset = target.for_card(card);
+
+            if is_wants_section {
+                (card_id, playset, copies, 0)
+            } else {
+                (card_id, playset.saturating_add(copies), 0, 0)
+            }
+        };
+
+        let Some(card) = known_cards.get(card_id.trim()) else {
+            continue;
+        };
+
+        // A card listed in both the wants and the trades keeps a single entry
+        match positions.get(card.id.as_str()) {
+            Some(&position) => {
+                let friend_card = &mut friend_cards[position];
+                friend_card.quantity = friend_card.quantity.max(quantity);
+                friend_card.is_owned = friend_card.quantity > 0;
+                friend_card.wanted = friend_card.wanted.saturating_add(wanted);
+                friend_card.for_trade = friend_card.for_trade.saturating_add(for_trade);
+            }
+            None => {
+                positions.insert(card.id.as_str(), friend_cards.len());
+                friend_cards.push(CollectionCard {
+                    card: (*card).clone(),
+                    is_owned: quantity > 0,
+                    quantity,
+                    wanted,
+                    for_trade,
+                });
+            }
+        }
+    }
+
+    if friend_cards.is_empty() {
+        return Err(TradeError::Empty);
+    }
+
+    Ok(friend_cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matching_copies, parse_friend_collection, propose_trade, TradeEntry, TradeError};
+    use crate::{
+        cards::{
+            tests::{card, collection_card},
+            Card, CardCategory,
+        },
+        collection::{CollectionCard, PlaysetTarget, ProgressionSettings, SpecialCardsDisplay},
+    };
+
+    fn our_cards() -> Vec<CollectionCard> {
+        let token = Card {
+            category: CardCategory::Token,
+            ..card("BP01-T01")
+        };
+
+        vec![
+            collection_card(card("BP01-001"), 5),
+            collection_card(card("BP01-002"), 0),
+            collection_card(token, 5),
+        ]
+    }
+
+    const FRIEND_TRADE_LIST: &str = "== Wants ==
+[BP01] Advent of Genesis
+2x BP01-001 BP01-001
+1x BP01-T01 BP01-T01
+
+== Trades ==
+[BP01] Advent of Genesis
+1x BP01-001 BP01-001
+2x BP01-002 BP01-002
+1x BP99-001 Unknown card
+";
+
+    fn entries(entries: &[TradeEntry]) -> Vec<(&str, u8)> {
+        entries
+            .iter()
+            .map(|entry| (entry.card.id.as_str(), entry.copies))
+            .collect()
+    }
+
+    #[test]
+    fn collection_file_is_parsed() {
+        let text = "# shadowverse-collection\nBP01-001;2;1;0\nBP99-001;3;0;0\n";
+
+        let friend_cards =
+            parse_friend_collection(text, &our_cards(), &PlaysetTarget::default()).unwrap();
+
+        assert_eq!(friend_cards.len(), 1);
+        assert_eq!(friend_cards[0].card.id, "BP01-001");
+        assert_eq!(friend_cards[0].quantity, 2);
+        assert_eq!(friend_cards[0].wanted, 1);
+        assert!(friend_cards[0].is_owned);
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        let text = "# shadowverse-collection\nBP01-001;2\n";
+
+        let error = parse_friend_collection(text, &our_cards(), &PlaysetTarget::default());
+
+        assert!(matches!(error, Err(TradeError::InvalidLine(2, _))));
+    }
+
+    #[test]
+    fn card_listed_in_wants_and_trades_has_a_single_entry() {
+        let friend_cards =
+            parse_friend_collection(FRIEND_TRADE_LIST, &our_cards(), &PlaysetTarget::default())
+                .unwrap();
+
+        let card = |id: &str| {
+            friend_cards
+                .iter()
+                .filter(|friend_card| friend_card.card.id == id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(friend_cards.len(), 3);
+        let [listed_twice] = card("BP01-001")[..] else {
+            panic!("BP01-001 must have a single entry");
+        };
+        assert_eq!(listed_twice.quantity, 4);
+        assert_eq!(listed_twice.wanted, 2);
+        assert_eq!(card("BP01-002")[0].quantity, 5);
+    }
+
+    #[test]
+    fn tokens_are_only_traded_when_they_are_counted() {
+        let ours = our_cards();
+        let theirs =
+            parse_friend_collection(FRIEND_TRADE_LIST, &ours, &PlaysetTarget::default()).unwrap();
+
+        assert_eq!(
+            entries(&matching_copies(
+                &ours,
+                &theirs,
+                &ProgressionSettings::default()
+            )),
+            vec![("BP01-001", 2)]
+        );
+        assert_eq!(
+            entries(&matching_copies(
+                &ours,
+                &theirs,
+                &ProgressionSettings {
+                    special_cards: SpecialCardsDisplay::Included,
+                    ..Default::default()
+                }
+            )),
+            vec![("BP01-001", 2), ("BP01-T01", 1)]
+        );
+    }
+
+    #[test]
+    fn proposed_trade_balances_the_copies_of_each_rarity() {
+        let ours = our_cards();
+        let theirs =
+            parse_friend_collection(FRIEND_TRADE_LIST, &ours, &PlaysetTarget::default()).unwrap();
+
+        let proposed_trade = propose_trade(&ours, &theirs, &ProgressionSettings::default());
+
+        assert_eq!(entries(&proposed_trade.given), vec![("BP01-001", 2)]);
+        assert_eq!(entries(&proposed_trade.received), vec![("BP01-002", 2)]);
+    }
+}
@@ -3,46 +3,123 @@ use std::sync::Arc;
 use data::{
     collection::ExtensionProgression,
     config::Config,
+    covers,
     db::{self, get_extensions},
+    themes,
 };
 use iced::{
     widget::{container, Row},
     Application, Command, Length, Subscription,
 };
 
-use crate::{screens, widgets::sidebar::sidebar};
+use crate::{screens, theme, widgets::sidebar::sidebar};
 
 #[derive(Debug, Clone)]
 pub enum ApplicationMessage {
     CardsListUpdater(screens::update::Message),
     ExtensionsList(screens::extensions_list::Message),
+    ExtensionDetails(screens::extension_details::Message),
     CardsList(screens::cards_list::Message),
+    Search(screens::search::Message),
+    Trades(screens::trades::Message),
+    Compare(screens::compare::Message),
+    Dashboard(screens::dashboard::Message),
+    Settings(screens::settings::Message),
     OnSidebarClick(String),
 }
 
 pub enum AppScreens {
     CardsListUpdater(screens::update::CardsUpdater),
     Extensions(screens::extensions_list::ExtensionsList),
+    ExtensionDetails(Box<screens::extension_details::ExtensionDetails>),
     CardsList(Box<screens::cards_list::CardsList>),
+    Search(screens::search::Search),
+    Trades(screens::trades::Trades),
+    Compare(screens::compare::Compare),
+    Dashboard(screens::dashboard::Dashboard),
+    Settings(Box<screens::settings::Settings>),
 }
 
 pub struct IcedApplication {
     config: Arc<Config>,
     screen: AppScreens,
+    theme: theme::Theme,
 }
 
 impl IcedApplication {
     fn navigate_to_extensions(&mut self) {
         let progression = get_extensions(&self.config);
-        self.screen =
-            AppScreens::Extensions(screens::extensions_list::ExtensionsList::new(progression));
+        self.screen = AppScreens::Extensions(screens::extensions_list::ExtensionsList::new(
+            progression,
+            self.config.progression_settings(),
+            self.config.extensions_list_settings(),
+        ));
+    }
+
+    fn navigate_to_extension_details(&mut self, extension_progression: &ExtensionProgression) {
+        self.screen = AppScreens::ExtensionDetails(Box::new(
+            screens::extension_details::ExtensionDetails::new(
+                extension_progression.clone(),
+                self.config.progression_settings(),
+                self.config.covers_directory.clone(),
+            ),
+        ))
     }
 
     fn navigate_to_progress(&mut self, extension_progression: &ExtensionProgression) {
+        let filter = db::get_extension_filter(&self.config, &extension_progression.extension.id);
         self.screen = AppScreens::CardsList(Box::new(screens::cards_list::CardsList::new(
             extension_progression.clone(),
+            filter,
+            db::get_screen_sort(&self.config, "cards_list"),
+            &self.config.table_layout("cards_list"),
+            self.config.progression_settings().special_cards,
+            self.config.covers_directory.clone(),
         )))
     }
+
+    fn navigate_to_search(&mut self) {
+        self.screen = AppScreens::Search(screens::search::Search::new(
+            db::get_screen_sort(&self.config, "search"),
+            &self.config.table_layout("search"),
+        ));
+    }
+
+    fn navigate_to_settings(&mut self) {
+        let image_cache = covers::sync_image_cache(&self.config).unwrap_or_else(|error| {
+            tracing::error!("Could not read the image cache: {}", error);
+            Default::default()
+        });
+        let configuration = self.config.configuration();
+        let themes = theme::Theme::names(&themes::load_user_themes(&self.config));
+        self.screen = AppScreens::Settings(Box::new(screens::settings::Settings::new(
+            configuration,
+            themes,
+            &self.config,
+            image_cache,
+        )));
+    }
+
+    fn navigate_to_dashboard(&mut self) {
+        match db::get_collection_stats(&self.config) {
+            Ok(stats) => {
+                self.screen = AppScreens::Dashboard(screens::dashboard::Dashboard::new(stats))
+            }
+            Err(error) => {
+                tracing::error!(
+                    "Could not compute the statistics of the collection: {}",
+                    error
+                )
+            }
+        }
+    }
+
+    fn navigate_to_trades(&mut self) {
+        self.screen = AppScreens::Trades(screens::trades::Trades::new(
+            get_extensions(&self.config),
+            &self.config.progression_settings(),
+        ));
+    }
 }
 
 impl Application for IcedApplication {
@@ -52,10 +129,15 @@ impl Application for IcedApplication {
     type Flags = Config;
 
     fn new(flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        let application = Self {
+        let sync_on_startup = flags.sync_settings().on_startup;
+        let mut application = Self {
+            theme: theme::Theme::from_config(&flags),
             config: Arc::new(flags),
             screen: AppScreens::CardsListUpdater(screens::update::CardsUpdater::new()),
         };
+        if !sync_on_startup {
+            application.navigate_to_extensions();
+        }
         (application, Command::none())
     }
 
@@ -63,6 +145,10 @@ impl Application for IcedApplication {
         "Shadowverse utils".into()
     }
 
+    fn theme(&self) -> Self::Theme {
+        self.theme.clone()
+    }
+
     fn update(&mut self, message: Self::Message) -> iced::Command<Self::Message> {
         match message {
             ApplicationMessage::CardsListUpdater(message) => {
@@ -91,12 +177,36 @@ impl Application for IcedApplication {
                 command
             }
             ApplicationMessage::ExtensionsList(message) => {
-                match message {
-                    screens::extensions_list::Message::ToDetails(extension_progression) => {
-                        self.navigate_to_progress(&extension_progression);
-                    }
+                if let screens::extensions_list::Message::ToDetails(extension_progression) =
+                    &message
+                {
+                    self.navigate_to_extension_details(extension_progression);
+                    return Command::none();
                 }
-                Command::none()
+
+                let AppScreens::Extensions(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                screen
+                    .update(&self.config, message)
+                    .map(ApplicationMessage::ExtensionsList)
+            }
+            ApplicationMessage::ExtensionDetails(message) => {
+                if let screens::extension_details::Message::ToCardsList(extension_progression) =
+                    &message
+                {
+                    self.navigate_to_progress(extension_progression);
+                    return Command::none();
+                }
+
+                let AppScreens::ExtensionDetails(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                screen
+                    .update(&self.config, message)
+                    .map(ApplicationMessage::ExtensionDetails)
             }
             ApplicationMessage::CardsList(message) => {
                 let AppScreens::CardsList(screen) = &mut self.screen else {
@@ -107,9 +217,87 @@ impl Application for IcedApplication {
                     .update(&self.config, message)
                     .map(ApplicationMessage::CardsList)
             }
+            ApplicationMessage::Search(message) => {
+                let AppScreens::Search(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                screen
+                    .update(&self.config, message)
+                    .map(ApplicationMessage::Search)
+            }
+            ApplicationMessage::Trades(message) => {
+                let AppScreens::Trades(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                screen.update(message).map(ApplicationMessage::Trades)
+            }
+            ApplicationMessage::Compare(message) => {
+                let AppScreens::Compare(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                screen
+                    .update(&self.config, message)
+                    .map(ApplicationMessage::Compare)
+            }
+            ApplicationMessage::Dashboard(message) => match message {},
+            ApplicationMessage::Settings(message) => {
+                let AppScreens::Settings(screen) = &mut self.screen else {
+                    return Command::none();
+                };
+
+                if let screens::settings::Message::SyncNow = message {
+                    self.screen =
+                        AppScreens::CardsListUpdater(screens::update::CardsUpdater::new());
+                    return Command::none();
+                }
+
+                if let screens::settings::Message::MoveData = message {
+                    let (data_directory, cache_directory) = screen.target_directories();
+                    let result = self.config.move_data(data_directory, cache_directory);
+                    let moved = match result {
+                        Ok(config) => {
+                            self.config = Arc::new(config);
+                            Ok(())
+                        }
+                        Err(error) => {
+                            tracing::error!("{}", error);
+                            Err(error.to_string())
+                        }
+                    };
+
+                    self.navigate_to_settings();
+                    if let AppScreens::Settings(screen) = &mut self.screen {
+                        screen.data_moved(moved);
+                    }
+                    return Command::none();
+                }
+
+                let is_save = matches!(message, screens::settings::Message::Save);
+                let command = screen
+                    .update(&self.config, message)
+                    .map(ApplicationMessage::Settings);
+
+                // The saved theme is applied without restarting, the screen shows the error when
+                // the configuration could not be written
+                if is_save && screen.is_saved() {
+                    self.theme = theme::Theme::from_config(&self.config);
+                }
+
+                command
+            }
             ApplicationMessage::OnSidebarClick(screen_key) => {
                 match screen_key.as_str() {
-                    "progressions" => self.navigate_to_extensions(),
+                    "progression" => self.navigate_to_extensions(),
+                    "dashboard" => self.navigate_to_dashboard(),
+                    "search" => self.navigate_to_search(),
+                    "settings" => self.navigate_to_settings(),
+                    "trades" => self.navigate_to_trades(),
+                    "compare" => {
+                        self.screen = AppScreens::Compare(screens::compare::Compare::new())
+                    }
                     _ => self.navigate_to_extensions(),
                 };
                 Command::none()
@@ -123,7 +311,15 @@ impl Application for IcedApplication {
                 screen.view().map(ApplicationMessage::CardsListUpdater)
             }
             AppScreens::Extensions(screen) => screen.view().map(ApplicationMessage::ExtensionsList),
+            AppScreens::ExtensionDetails(screen) => {
+                screen.view().map(ApplicationMessage::ExtensionDetails)
+            }
             AppScreens::CardsList(screen) => screen.view().map(ApplicationMessage::CardsList),
+            AppScreens::Search(screen) => screen.view().map(ApplicationMessage::Search),
+            AppScreens::Trades(screen) => screen.view().map(ApplicationMessage::Trades),
+            AppScreens::Compare(screen) => screen.view().map(ApplicationMessage::Compare),
+            AppScreens::Dashboard(screen) => screen.view().map(ApplicationMessage::Dashboard),
+            AppScreens::Settings(screen) => screen.view().map(ApplicationMessage::Settings),
         };
 
         let sidebar_option = match &self.screen {
@@ -0,0 +1,12 @@
+use iced::futures::channel::oneshot;
+
+/// Run blocking work, like a database query or the decoding of an image, on its own thread so
+/// the executor of the application is not blocked
+pub async fn spawn_blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> T {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(work());
+    });
+
+    receiver.await.expect("The blocking work panicked")
+}
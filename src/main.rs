@@ -1,18 +1,39 @@
-use data::{config::Config, db::setup_db};
+use data::{config::Config, db::setup_db, environment::PathOverrides};
 use iced::{window, Application, Settings, Size};
 
 mod app;
+mod blocking;
 mod logger;
 mod screens;
+mod startup_error;
 mod theme;
 mod widget;
 mod widgets;
 
 fn main() -> Result<(), iced::Error> {
-    let config = Config::load().unwrap();
+    let overrides = PathOverrides::from_environment().with_args(std::env::args_os().skip(1));
+    let override_warnings = overrides.warnings.clone();
+    let config = match Config::load_with(overrides) {
+        Ok(config) => config,
+        Err(error) => {
+            // The logger needs the configuration, so the warnings can only be printed
+            for warning in override_warnings {
+                eprintln!("{}", warning);
+            }
+            eprintln!("Could not load the configuration: {}", error);
+            return show_startup_error(error.to_string());
+        }
+    };
     logger::init_logger(config.log_file.clone());
+    for warning in &config.load_warnings {
+        tracing::warn!("{}", warning);
+    }
 
-    let _ = setup_db(&config);
+    // The screens can not work without the tables and the search index
+    if let Err(error) = setup_db(&config) {
+        tracing::error!("{}", error);
+        return show_startup_error(error.to_string());
+    }
 
     let default_settings = Settings::<()>::default();
     let window = window::Settings {
@@ -34,3 +55,17 @@ fn main() -> Result<(), iced::Error> {
 
     app::IcedApplication::run(settings)
 }
+
+/// Replace the application by a window explaining why it could not start
+fn show_startup_error(error: String) -> Result<(), iced::Error> {
+    startup_error::StartupError::run(Settings {
+        window: window::Settings {
+            size: Size {
+                width: 800.0,
+                height: 400.0,
+            },
+            ..Default::default()
+        },
+        ..Settings::with_flags(error)
+    })
+}
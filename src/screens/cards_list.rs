@@ -1,296 +1,957 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 use data::{
-    cards::{Card, CardClass},
-    collection::{CollectionCard, ExtensionProgression},
-    config::Config,
-    db::get_extension,
+    cards::{CardClass, CardType, Rarity},
+    collection::{CollectionCard, ExtensionProgression, SpecialCardsDisplay},
+    config::{ColumnLayout, Config},
+    covers::{displayed_cover, generate_thumbnail, thumbnail_path, track_card_images},
+    db::{get_extension, save_extension_filter, save_screen_sort},
+    filter::{CardsFilter, EvolutionFilter, OwnershipFilter, RangeFilter},
+    printing::playable_copies,
+    sort::CardsSort,
 };
 use iced::{
     keyboard::key::Named,
-    widget::{column, combo_box, container, row, scrollable, text, text_input, Row, Svg},
+    widget::{button, column, combo_box, container, image, row, text, text_input, Row, Svg},
     Command, Length, Subscription,
 };
 use widgets::header::Column;
-use widgets::table_row::TableRow;
+use widgets::{
+    card_tile::CardTile,
+    stepper::{self, Stepper},
+    table_row::TableRow,
+    virtual_list::{scroll_into_view, VirtualList},
+};
+
+use crate::{
+    blocking::spawn_blocking,
+    theme::Theme,
+    widget::Element,
+    widgets::{
+        chip::chip,
+        class_badge::{class_badge, class_chip},
+        table_header::{
+            apply_layout, column_sort_field, columns_layout, move_column, resize_column,
+            table_header,
+        },
+    },
+};
 
-use crate::{theme::Theme, widget::Element};
+/// Key used to remember the sort and the columns of the table
+const TABLE_KEY: &str = "cards_list";
+/// Id of the list of cards, used to scroll to the focused quantity
+const CARDS_LIST_ID: &str = "cards_list";
+/// Number of cards on each line of the gallery
+const GALLERY_COLUMNS: usize = 5;
+const TILE_WIDTH: f32 = 120.0;
+const TILE_HEIGHT: f32 = 168.0;
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    UpdateQuantity(String, String),
+    UpdateQuantity(String, u8),
+    UpdateWanted(String, u8),
+    UpdateForTrade(String, u8),
+    CommitQuantity(String),
+    QuantityFocused(String),
+    QuantityBlurred(String),
     Selected(CardClass),
     FilterByName(String),
-    TabPressed,
-    ShiftTabPressed,
+    ToggleRarity(Rarity),
+    ToggleCardType(CardType),
+    SelectTrait(String),
+    RemoveTrait(String),
+    RangeChanged(RangeField, RangeBound, String),
+    ToggleEvolution(EvolutionFilter),
+    ToggleOwnership(OwnershipFilter),
+    ToggleAdvancedFilters,
+    ResetFilters,
+    SortBy(usize),
+    ResizeColumn(usize, f32),
+    MoveColumn(usize, usize),
+    SaveColumns,
+    FocusNextQuantity,
+    FocusPreviousQuantity,
+    ToggleGallery,
+    /// Show the other form of the card in the gallery, the evolved form of a follower or the
+    /// base of an evolved card
+    FlipCard(String),
+    ThumbnailsGenerated(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RangeField {
+    Cost,
+    Attack,
+    Hp,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RangeBound {
+    Min,
+    Max,
 }
 
 pub struct CardsList {
     columns: Vec<Column>,
     extension_progression: ExtensionProgression,
 
-    filter_name: String,
-    filter_cards_classes: iced::widget::combo_box::State<CardClass>,
-    filter_card_class: Option<CardClass>,
+    filter: CardsFilter,
+    filter_rarities: Vec<Rarity>,
+    filter_card_types: Vec<CardType>,
+    filter_traits: iced::widget::combo_box::State<String>,
+    show_advanced_filters: bool,
+    show_gallery: bool,
+    /// Cards whose gallery tile shows their other form
+    flipped_cards: HashSet<String>,
+    covers_directory: PathBuf,
+    sort: Option<CardsSort>,
+    special_cards: SpecialCardsDisplay,
 
     filtered_cards_list: Vec<CollectionCard>,
-    quantities: HashMap<String, String>,
+    /// Copies of each card counting all its printings in the extension
+    playable_copies: HashMap<String, u32>,
     selected_quantity_textinput_id: Option<String>,
+    /// Card whose quantity stepper has the focus, the navigation keys are only bound while it is set
+    focused_quantity: Option<String>,
+    /// Quantity edited in the focused stepper, saved when it is committed or loses the focus
+    edited_quantity: Option<(String, u8)>,
 }
 
 impl CardsList {
-    pub fn new(extension_progression: ExtensionProgression) -> Self {
-        let mut quantities: HashMap<String, String> =
-            HashMap::with_capacity(extension_progression.extension_cards.len());
-        extension_progression
-            .extension_cards
+    pub fn new(
+        extension_progression: ExtensionProgression,
+        filter: CardsFilter,
+        sort: Option<CardsSort>,
+        columns_layout: &[ColumnLayout],
+        special_cards: SpecialCardsDisplay,
+        covers_directory: PathBuf,
+    ) -> Self {
+        let cards = extension_progression.cards();
+        let rarities: BTreeSet<Rarity> = cards.iter().map(|card| card.rarity.clone()).collect();
+        let card_types: BTreeSet<CardType> =
+            cards.iter().map(|card| card.card_type.clone()).collect();
+        let traits: BTreeSet<String> = cards
             .iter()
-            .for_each(|extension_card| {
-                let card = extension_card.card.clone();
-                let quantity = extension_card.quantity.to_string();
-                quantities.insert(card.id.clone(), quantity);
-            });
-        Self {
-            columns: vec![
-                Column::new("Owned").width(Length::FillPortion(1)),
-                Column::new("Rarity").width(Length::FillPortion(2)),
-                Column::new("Number").width(Length::FillPortion(2)),
-                Column::new("Name").width(Length::FillPortion(4)),
-                Column::new("Class").width(Length::FillPortion(2)),
-                Column::new("Quantity").width(Length::Fixed(100.0)),
-            ],
+            .flat_map(|card| card.traits.iter().cloned())
+            .collect();
+
+        let mut cards_list = Self {
+            columns: apply_layout(
+                vec![
+                    Column::new("Owned")
+                        .width(Length::FillPortion(1))
+                        .sortable(false),
+                    Column::new("Rarity").width(Length::FillPortion(2)),
+                    Column::new("Number").width(Length::FillPortion(2)),
+                    Column::new("Name").width(Length::FillPortion(4)),
+                    Column::new("Class").width(Length::FillPortion(2)),
+                    Column::new("Cost").width(Length::FillPortion(1)),
+                    Column::new("Other form")
+                        .width(Length::Fixed(130.0))
+                        .sortable(false),
+                    Column::new("Quantity").width(Length::Fixed(120.0)),
+                    Column::new("Playable")
+                        .width(Length::Fixed(90.0))
+                        .sortable(false),
+                    Column::new("Wanted")
+                        .width(Length::Fixed(120.0))
+                        .sortable(false),
+                    Column::new("Trade")
+                        .width(Length::Fixed(120.0))
+                        .sortable(false),
+                ],
+                columns_layout,
+            ),
             filtered_cards_list: extension_progression.clone().extension_cards,
-            quantities,
+            playable_copies: HashMap::new(),
             extension_progression,
-            filter_name: String::new(),
-            filter_cards_classes: combo_box::State::new(CardClass::ALL.to_vec()),
-            filter_card_class: None,
+            show_advanced_filters: !filter.is_empty(),
+            show_gallery: false,
+            flipped_cards: HashSet::new(),
+            covers_directory,
+            sort,
+            special_cards,
+            filter,
+            filter_rarities: rarities.into_iter().collect(),
+            filter_card_types: card_types.into_iter().collect(),
+            filter_traits: combo_box::State::new(traits.into_iter().collect()),
             selected_quantity_textinput_id: None,
-        }
+            focused_quantity: None,
+            edited_quantity: None,
+        };
+        cards_list.filter_cards_list();
+        cards_list
     }
 
     pub fn update(&mut self, config: &Config, message: Message) -> Command<Message> {
         match message {
             Message::UpdateQuantity(card_id, quantity) => {
-                self.quantities.insert(card_id.clone(), quantity.clone());
+                if self.focused_quantity.as_ref() == Some(&card_id) {
+                    self.edit_quantity(config, card_id, quantity);
+                } else {
+                    self.save_quantity(config, &card_id, quantity);
+                }
 
-                let Ok(quantity) = quantity.parse::<u8>() else {
-                    return Command::none();
-                };
+                Command::none()
+            }
+            Message::UpdateWanted(card_id, wanted) => {
+                if let Err(error) = data::db::update_card_wanted(config, &card_id, wanted) {
+                    tracing::error!(
+                        "Could not update the wanted copies of {}: {}",
+                        card_id,
+                        error
+                    );
+                }
+                self.reload_extension(config);
 
-                let _ = data::db::update_card_quantity(config, &card_id, quantity);
+                Command::none()
+            }
+            Message::UpdateForTrade(card_id, for_trade) => {
+                if let Err(error) = data::db::update_card_for_trade(config, &card_id, for_trade) {
+                    tracing::error!(
+                        "Could not update the copies to trade of {}: {}",
+                        card_id,
+                        error
+                    );
+                }
+                self.reload_extension(config);
 
-                self.extension_progression =
-                    get_extension(config, &self.extension_progression.extension.id);
-                self.filtered_cards_list
-                    .clone_from(&self.extension_progression.extension_cards);
-                self.filter_cards_list();
+                Command::none()
+            }
+            Message::CommitQuantity(card_id) => {
+                self.commit_quantity(config);
+                self.selected_quantity_textinput_id = Some(card_id);
+
+                self.focus_next_quantity(1)
+            }
+            Message::QuantityFocused(card_id) => {
+                if self
+                    .edited_quantity
+                    .as_ref()
+                    .is_some_and(|(edited_id, _)| *edited_id != card_id)
+                {
+                    self.commit_quantity(config);
+                }
+                self.selected_quantity_textinput_id = Some(card_id.clone());
+                self.focused_quantity = Some(card_id);
+
+                Command::none()
+            }
+            Message::QuantityBlurred(card_id) => {
+                if self
+                    .edited_quantity
+                    .as_ref()
+                    .is_some_and(|(edited_id, _)| *edited_id == card_id)
+                {
+                    self.commit_quantity(config);
+                }
+                // The next stepper may have been focused before this one was blurred
+                if self.focused_quantity.as_ref() == Some(&card_id) {
+                    self.focused_quantity = None;
+                }
 
                 Command::none()
             }
             Message::Selected(card_class) => {
-                self.filter_card_class = Some(card_class);
-                self.filter_cards_list();
+                // Selecting the class again removes the filter
+                self.filter.card_class =
+                    (self.filter.card_class != Some(card_class)).then_some(card_class);
+                self.apply_filter(config);
 
                 Command::none()
             }
             Message::FilterByName(card_name) => {
-                self.filter_name = card_name;
+                self.filter.name = card_name;
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::ToggleRarity(rarity) => {
+                toggle(&mut self.filter.rarities, rarity);
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::ToggleCardType(card_type) => {
+                toggle(&mut self.filter.card_types, card_type);
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::SelectTrait(card_trait) => {
+                if !self.filter.traits.contains(&card_trait) {
+                    self.filter.traits.push(card_trait);
+                }
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::RemoveTrait(card_trait) => {
+                self.filter
+                    .traits
+                    .retain(|selected| selected != &card_trait);
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::RangeChanged(field, bound, value) => {
+                let value = if value.is_empty() {
+                    None
+                } else {
+                    let Ok(value) = value.parse::<u8>() else {
+                        return Command::none();
+                    };
+                    Some(value)
+                };
+
+                let range = self.range_mut(field);
+                match bound {
+                    RangeBound::Min => range.min = value,
+                    RangeBound::Max => range.max = value,
+                }
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::ToggleEvolution(evolution) => {
+                toggle(&mut self.filter.evolutions, evolution);
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::ToggleOwnership(ownership) => {
+                toggle(&mut self.filter.ownerships, ownership);
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::ToggleAdvancedFilters => {
+                self.show_advanced_filters = !self.show_advanced_filters;
+
+                Command::none()
+            }
+            Message::ResetFilters => {
+                self.filter = CardsFilter::default();
+                self.apply_filter(config);
+
+                Command::none()
+            }
+            Message::SortBy(column_index) => {
+                let Some(field) = self.columns.get(column_index).and_then(column_sort_field) else {
+                    return Command::none();
+                };
+
+                self.sort = CardsSort::next(self.sort, field);
                 self.filter_cards_list();
 
+                if let Err(error) = save_screen_sort(config, TABLE_KEY, self.sort.as_ref()) {
+                    tracing::error!("Could not save the sort of the cards list: {}", error);
+                }
+
+                Command::none()
+            }
+            Message::ResizeColumn(column_index, width) => {
+                resize_column(&mut self.columns, column_index, width);
+
+                Command::none()
+            }
+            Message::MoveColumn(from, to) => {
+                move_column(&mut self.columns, from, to);
+                self.save_columns(config);
+
+                Command::none()
+            }
+            Message::SaveColumns => {
+                self.save_columns(config);
+
                 Command::none()
             }
-            Message::TabPressed => {
-                let cards_list = &self.filtered_cards_list.clone();
-
-                // if self.selected_quantity_textinput_id.is_none() {
-                // Select the first one
-                let first_element = cards_list.iter().next().unwrap();
-                let card_id = &first_element.card.id;
-                tracing::info!("Focusing the text input {}", card_id);
-                // self.selected_quantity_textinput_id = Some(card_id.clone());
-                return Command::batch([
-                    text_input::focus(text_input::Id::new("BP01-001EN")),
-                    text_input::select_all(text_input::Id::new(card_id.clone())),
-                ]);
-                // }
-                //
-                // let current_card_id = self.selected_quantity_textinput_id.clone().unwrap();
-                // let position = cards_list
-                //     .iter()
-                //     .position(|card| card.card.id.clone() == current_card_id)
-                //     .unwrap();
-                //
-                // let next = cards_list
-                //     .iter()
-                //     .skip(std::cmp::max(position, 1))
-                //     .next()
-                //     .unwrap();
-                //
-                // tracing::info!("Focusing the text input {}", &next.card.id);
-                // // self.selected_quantity_textinput_id = Some(next.clone().card.id);
-                // return iced::widget::text_input::select_all(iced::widget::text_input::Id::new(
-                //     next.card.id.clone(),
-                // ));
+            Message::FlipCard(card_id) => {
+                if !self.flipped_cards.remove(&card_id) {
+                    self.flipped_cards.insert(card_id);
+                }
+
+                Command::none()
             }
-            Message::ShiftTabPressed => {
-                if self.selected_quantity_textinput_id.is_none() {
+            Message::ToggleGallery => {
+                self.show_gallery = !self.show_gallery;
+                if !self.show_gallery {
                     return Command::none();
                 }
+
+                // Covers are shown at a small size, the thumbnails are faster to render
+                let covers_directory = config.covers_directory.clone();
+                let cards_ids: Vec<String> = self
+                    .extension_progression
+                    .extension_cards
+                    .iter()
+                    .map(|extension_card| extension_card.card.id.clone())
+                    .collect();
+                // Decoding and resizing the covers blocks, it is done on its own thread
+                Command::perform(
+                    spawn_blocking(move || {
+                        cards_ids
+                            .into_iter()
+                            .filter(|card_id| {
+                                !thumbnail_path(&covers_directory, card_id).exists()
+                                    && generate_thumbnail(&covers_directory, card_id).is_ok()
+                            })
+                            .collect()
+                    }),
+                    Message::ThumbnailsGenerated,
+                )
+            }
+            Message::ThumbnailsGenerated(cards_ids) => {
+                for card_id in cards_ids {
+                    if let Err(error) = track_card_images(config, &card_id) {
+                        tracing::error!("{}", error);
+                    }
+                }
+
                 Command::none()
             }
+            Message::FocusNextQuantity => {
+                self.commit_quantity(config);
+                self.focus_next_quantity(1)
+            }
+            Message::FocusPreviousQuantity => {
+                self.commit_quantity(config);
+                self.focus_next_quantity(-1)
+            }
         }
     }
 
     pub fn view(&self) -> Element<'_, Message> {
+        let advanced_filters_label = if self.show_advanced_filters {
+            "Less filters"
+        } else {
+            "More filters"
+        };
         let filters = row![
-            text_input("Type the card name here", &self.filter_name)
+            text_input("Type the card name here", &self.filter.name)
                 .width(Length::FillPortion(3))
                 .on_input(Message::FilterByName),
-            combo_box(
-                &self.filter_cards_classes,
-                "Select the card class",
-                self.filter_card_class.as_ref(),
-                Message::Selected
-            )
-            .width(Length::FillPortion(1))
+            Row::with_children(CardClass::ALL.iter().map(|class| {
+                class_chip(
+                    *class,
+                    self.filter.card_class == Some(*class),
+                    Message::Selected(*class),
+                )
+            }))
+            .spacing(5.0),
+            button(text(advanced_filters_label)).on_press(Message::ToggleAdvancedFilters),
+            button(text(if self.show_gallery {
+                "Table"
+            } else {
+                "Gallery"
+            }))
+            .on_press(Message::ToggleGallery),
+            button(text("Reset")).on_press(Message::ResetFilters),
         ]
         .spacing(15.0)
         .padding(15.0)
+        .align_items(iced::Alignment::Center)
         .height(Length::Fixed(70.0))
         .into();
-        let cards_list = cards_list(&self.columns, &self.filtered_cards_list, &self.quantities);
+        let cards_list = if self.show_gallery {
+            cards_gallery(
+                &self.filtered_cards_list,
+                &self.extension_progression.extension_cards,
+                &self.flipped_cards,
+                &self.covers_directory,
+            )
+        } else {
+            cards_list(
+                &self.columns,
+                self.sort,
+                &self.filtered_cards_list,
+                &self.playable_copies,
+            )
+        };
+
+        let mut content = vec![filters];
+        if self.show_advanced_filters {
+            content.push(self.advanced_filters());
+        }
+        content.push(cards_list);
+
+        container(column(content)).max_width(800.0).into()
+    }
+
+    fn advanced_filters(&self) -> Element<'_, Message> {
+        let rarities = self
+            .filter_rarities
+            .iter()
+            .map(|rarity| {
+                chip(
+                    rarity.to_string(),
+                    self.filter.rarities.contains(rarity),
+                    Message::ToggleRarity(rarity.clone()),
+                )
+            })
+            .collect();
+
+        let card_types = self
+            .filter_card_types
+            .iter()
+            .map(|card_type| {
+                chip(
+                    card_type.to_string(),
+                    self.filter.card_types.contains(card_type),
+                    Message::ToggleCardType(card_type.clone()),
+                )
+            })
+            .collect();
+
+        let mut traits: Vec<Element<'_, Message>> = vec![combo_box(
+            &self.filter_traits,
+            "Add a trait",
+            None,
+            Message::SelectTrait,
+        )
+        .width(Length::Fixed(200.0))
+        .into()];
+        traits.extend(self.filter.traits.iter().map(|card_trait| {
+            chip(
+                format!("{} x", card_trait),
+                true,
+                Message::RemoveTrait(card_trait.clone()),
+            )
+        }));
+
+        let ranges = row![
+            range_filter("Cost", RangeField::Cost, &self.filter.cost),
+            range_filter("Attack", RangeField::Attack, &self.filter.attack),
+            range_filter("HP", RangeField::Hp, &self.filter.hp),
+        ]
+        .spacing(20.0);
+
+        let evolutions = EvolutionFilter::ALL
+            .iter()
+            .map(|evolution| {
+                chip(
+                    evolution.to_string(),
+                    self.filter.evolutions.contains(evolution),
+                    Message::ToggleEvolution(*evolution),
+                )
+            })
+            .collect();
+
+        let ownerships = OwnershipFilter::ALL
+            .iter()
+            .map(|ownership| {
+                chip(
+                    ownership.to_string(),
+                    self.filter.ownerships.contains(ownership),
+                    Message::ToggleOwnership(*ownership),
+                )
+            })
+            .collect();
+
+        column![
+            filter_line("Rarity", rarities),
+            filter_line("Type", card_types),
+            filter_line("Trait", traits),
+            ranges,
+            row![
+                filter_line("Form", evolutions),
+                filter_line("Status", ownerships)
+            ]
+            .spacing(20.0),
+        ]
+        .spacing(10.0)
+        .padding([0.0, 15.0])
+        .into()
+    }
+
+    fn save_quantity(&mut self, config: &Config, card_id: &str, quantity: u8) {
+        if let Err(error) = data::db::update_card_quantity(config, card_id, quantity) {
+            tracing::error!("Could not update the quantity of {}: {}", card_id, error);
+        }
+        self.reload_extension(config);
+    }
+
+    /// Keep the quantity typed in the focused stepper until it is committed, the quantity of
+    /// another card that was still being edited is saved first
+    fn edit_quantity(&mut self, config: &Config, card_id: String, quantity: u8) {
+        if self
+            .edited_quantity
+            .as_ref()
+            .is_some_and(|(edited_id, _)| *edited_id != card_id)
+        {
+            self.commit_quantity(config);
+        }
+
+        if let Some(extension_card) = self
+            .filtered_cards_list
+            .iter_mut()
+            .find(|extension_card| extension_card.card.id == card_id)
+        {
+            extension_card.quantity = quantity;
+        }
+        self.edited_quantity = Some((card_id, quantity));
+    }
+
+    fn commit_quantity(&mut self, config: &Config) {
+        if let Some((card_id, quantity)) = self.edited_quantity.take() {
+            self.save_quantity(config, &card_id, quantity);
+        }
+    }
 
-        container(column(vec![filters, cards_list]))
-            .max_width(800.0)
-            .into()
+    fn reload_extension(&mut self, config: &Config) {
+        self.extension_progression =
+            get_extension(config, &self.extension_progression.extension.id);
+        self.filtered_cards_list
+            .clone_from(&self.extension_progression.extension_cards);
+        self.filter_cards_list();
+    }
+
+    /// Focus the quantity input `step` rows after the selected one in the filtered cards,
+    /// wrapping around the list, and scroll the list to make it visible
+    fn focus_next_quantity(&mut self, step: isize) -> Command<Message> {
+        let cards_count = self.filtered_cards_list.len() as isize;
+        if cards_count == 0 {
+            return Command::none();
+        }
+
+        let selected_position =
+            self.selected_quantity_textinput_id
+                .as_ref()
+                .and_then(|selected_id| {
+                    self.filtered_cards_list
+                        .iter()
+                        .position(|extension_card| &extension_card.card.id == selected_id)
+                });
+        let next_position = match selected_position {
+            Some(position) => (position as isize + step).rem_euclid(cards_count),
+            None if step < 0 => cards_count - 1,
+            None => 0,
+        } as usize;
+
+        let card_id = self.filtered_cards_list[next_position].card.id.clone();
+        tracing::debug!("Focusing the text input {}", card_id);
+        self.selected_quantity_textinput_id = Some(card_id.clone());
+
+        Command::batch([
+            Command::widget(scroll_into_view(
+                iced::widget::scrollable::Id::new(CARDS_LIST_ID).into(),
+                next_position,
+            )),
+            Command::widget(stepper::focus(quantity_stepper_id(card_id))),
+        ])
+    }
+
+    fn save_columns(&self, config: &Config) {
+        if let Err(error) = config.save_table_layout(TABLE_KEY, columns_layout(&self.columns)) {
+            tracing::error!("Could not save the columns of the cards list: {}", error);
+        }
+    }
+
+    fn range_mut(&mut self, field: RangeField) -> &mut RangeFilter {
+        match field {
+            RangeField::Cost => &mut self.filter.cost,
+            RangeField::Attack => &mut self.filter.attack,
+            RangeField::Hp => &mut self.filter.hp,
+        }
+    }
+
+    fn apply_filter(&mut self, config: &Config) {
+        self.filter_cards_list();
+
+        let extension_id = &self.extension_progression.extension.id;
+        if let Err(error) = save_extension_filter(config, extension_id, &self.filter) {
+            tracing::error!("Could not save the filters of {}: {}", extension_id, error);
+        }
     }
 
     fn filter_cards_list(&mut self) {
+        self.playable_copies = playable_copies(&self.extension_progression.extension_cards);
         self.filtered_cards_list = self
             .extension_progression
             .clone()
             .extension_cards
             .into_iter()
             .filter(|extension_card| {
-                let name_contains_search = extension_card.card.name().contains(&self.filter_name);
-                if let Some(class_search) = self.filter_card_class.as_ref() {
-                    return name_contains_search && &extension_card.card.card_class == class_search;
-                }
-                name_contains_search
+                self.special_cards != SpecialCardsDisplay::Excluded
+                    || extension_card.card.category.is_collectible()
             })
+            .filter(|extension_card| self.filter.matches(extension_card))
             .collect();
-
-        let mut quantities: HashMap<String, String> =
-            HashMap::with_capacity(self.filtered_cards_list.len());
-        self.filtered_cards_list.iter().for_each(|extension_card| {
-            let card = extension_card.card.clone();
-            let quantity = extension_card.quantity.to_string();
-            quantities.insert(card.id, quantity);
-        });
-        self.quantities = quantities;
+        if let Some(sort) = &self.sort {
+            sort.sort(&mut self.filtered_cards_list);
+        }
+        if self.special_cards == SpecialCardsDisplay::Separated {
+            // The sort is stable, the tokens and leaders keep their order after the other cards
+            self.filtered_cards_list
+                .sort_by_key(|extension_card| !extension_card.card.category.is_collectible());
+        }
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        iced::keyboard::on_key_release(|key, modifiers| match key {
+        // The other inputs, like the name filter, keep the navigation keys
+        if self.focused_quantity.is_none() {
+            return Subscription::none();
+        }
+
+        iced::keyboard::on_key_press(|key, modifiers| match key {
             iced::keyboard::Key::Named(Named::Tab) => Some(if modifiers.shift() {
-                Message::ShiftTabPressed
+                Message::FocusPreviousQuantity
             } else {
-                Message::TabPressed
+                Message::FocusNextQuantity
             }),
+            iced::keyboard::Key::Named(Named::ArrowDown) => Some(Message::FocusNextQuantity),
+            iced::keyboard::Key::Named(Named::ArrowUp) => Some(Message::FocusPreviousQuantity),
             _ => None,
         })
     }
 }
 
-fn cards_list<'a>(
-    columns: &[Column],
-    collection_cards: &'a [CollectionCard],
-    quantities: &'a HashMap<String, String>,
-) -> Element<'a, Message> {
-    let headers = headers(columns);
-    let card_rows: Vec<Element<'a, Message>> = collection_cards
-        .iter()
-        .map(|collection_card| {
-            let quantity = quantities.get(&collection_card.card.id);
-            let default_quantity = "0".to_string();
-            table_row(
-                &collection_card.card,
-                collection_card.is_owned,
-                quantity.unwrap_or(&default_quantity).clone(),
-            )
-            .into()
-        })
-        .collect();
-    scrollable(
-        column(vec![headers, column(card_rows).spacing(6.0).into()])
-            .spacing(10.0)
-            .padding(15.0),
-    )
-    .height(Length::Fill)
-    .into()
+fn toggle<T: PartialEq>(selection: &mut Vec<T>, value: T) {
+    if let Some(position) = selection.iter().position(|selected| selected == &value) {
+        selection.remove(position);
+    } else {
+        selection.push(value);
+    }
 }
 
-fn headers<'a>(columns: &[Column]) -> Element<'a, Message> {
-    let columns: Vec<Element<'a, Message>> = columns
-        .iter()
-        .map(|column| text(column.name.to_string()).width(column.width).into())
-        .collect();
-    row(columns).into()
+fn filter_line<'a>(label: &str, options: Vec<Element<'a, Message>>) -> Element<'a, Message> {
+    row![
+        text(label.to_string()).width(Length::Fixed(60.0)),
+        Row::with_children(options)
+            .spacing(5.0)
+            .align_items(iced::Alignment::Center)
+    ]
+    .spacing(10.0)
+    .align_items(iced::Alignment::Center)
+    .into()
 }
 
-fn table_row(
-    card: &Card,
-    is_owned: bool,
-    quantity: String,
-) -> TableRow<'_, Message, Theme, iced::Renderer> {
-    let mut elements_row = Row::new().padding([0.0, 10.0]);
+fn range_filter<'a>(label: &str, field: RangeField, range: &RangeFilter) -> Element<'a, Message> {
+    let min = range.min.map(|min| min.to_string()).unwrap_or_default();
+    let max = range.max.map(|max| max.to_string()).unwrap_or_default();
 
-    let owned_graphic = if is_owned {
-        Svg::new("resources/done.svg")
-    } else {
-        Svg::new("resources/close.svg")
-    };
-    elements_row = elements_row.push(
-        owned_graphic
-            .width(Length::FillPortion(1))
-            .height(Length::Fill),
+    row![
+        text(label.to_string()).width(Length::Fixed(60.0)),
+        text_input("Min", &min)
+            .width(Length::Fixed(50.0))
+            .on_input(move |value| Message::RangeChanged(field, RangeBound::Min, value)),
+        text("-"),
+        text_input("Max", &max)
+            .width(Length::Fixed(50.0))
+            .on_input(move |value| Message::RangeChanged(field, RangeBound::Max, value)),
+    ]
+    .spacing(5.0)
+    .align_items(iced::Alignment::Center)
+    .into()
+}
+
+fn cards_list<'a>(
+    columns: &'a [Column],
+    sort: Option<CardsSort>,
+    collection_cards: &'a [CollectionCard],
+    playable_copies: &'a HashMap<String, u32>,
+) -> Element<'a, Message> {
+    let headers = table_header(
+        columns,
+        sort,
+        Message::SortBy,
+        Message::ResizeColumn,
+        Message::SaveColumns,
+        Message::MoveColumn,
     );
+    let card_rows = VirtualList::new(collection_cards.len(), move |index| {
+        let collection_card = &collection_cards[index];
+        let playable_copies = playable_copies
+            .get(&collection_card.card.id)
+            .copied()
+            .unwrap_or(collection_card.quantity as u32);
+        table_row(columns, collection_card, playable_copies).into()
+    })
+    .row_key(move |index| &collection_cards[index].card.id)
+    .id(iced::widget::scrollable::Id::new(CARDS_LIST_ID))
+    .row_height(35.0)
+    .spacing(6.0);
 
-    let card_rariry = text(card.rarity.clone())
-        .width(Length::FillPortion(2))
+    column(vec![headers, card_rows.into()])
+        .spacing(10.0)
+        .padding(15.0)
         .height(Length::Fill)
-        .vertical_alignment(iced::alignment::Vertical::Center);
-    elements_row = elements_row.push(card_rariry);
+        .into()
+}
 
-    let card_number = text(card.id.clone())
-        .width(Length::FillPortion(2))
-        .height(Length::Fill)
-        .vertical_alignment(iced::alignment::Vertical::Center);
-    elements_row = elements_row.push(card_number);
+fn cards_gallery<'a>(
+    collection_cards: &'a [CollectionCard],
+    extension_cards: &'a [CollectionCard],
+    flipped_cards: &'a HashSet<String>,
+    covers_directory: &'a Path,
+) -> Element<'a, Message> {
+    // Other form of each card of the extension, to flip the tiles
+    let linked_card = move |collection_card: &CollectionCard| {
+        let linked_id = collection_card.card.evolution.as_ref()?;
+        extension_cards
+            .iter()
+            .find(|extension_card| &extension_card.card.id == linked_id)
+    };
 
-    let card_name = text(card.name().clone())
-        .width(Length::FillPortion(4))
-        .height(Length::Fill)
-        .vertical_alignment(iced::alignment::Vertical::Center);
-    elements_row = elements_row.push(card_name);
+    let rows_count = collection_cards.len().div_ceil(GALLERY_COLUMNS);
+    let card_rows = VirtualList::new(rows_count, move |index| {
+        let start = index * GALLERY_COLUMNS;
+        let end = (start + GALLERY_COLUMNS).min(collection_cards.len());
+        let tiles: Vec<Element<'a, Message>> = collection_cards[start..end]
+            .iter()
+            .map(|collection_card| {
+                let linked_card = linked_card(collection_card);
+                let displayed_card = match linked_card {
+                    Some(linked_card) if flipped_cards.contains(&collection_card.card.id) => {
+                        linked_card
+                    }
+                    _ => collection_card,
+                };
+                let on_flip = linked_card
+                    .is_some()
+                    .then(|| Message::FlipCard(collection_card.card.id.clone()));
+
+                card_tile(displayed_card, on_flip, covers_directory)
+            })
+            .collect();
+
+        Row::with_children(tiles).spacing(15.0).into()
+    })
+    .row_key(move |index| {
+        let start = index * GALLERY_COLUMNS;
+        let end = (start + GALLERY_COLUMNS).min(collection_cards.len());
+        collection_cards[start..end]
+            .iter()
+            .map(|collection_card| &collection_card.card.id)
+            .collect::<Vec<_>>()
+    })
+    .row_height(TILE_HEIGHT + 25.0)
+    .spacing(10.0);
 
-    let class = text(format!("{:?}", card.card_class))
-        .width(Length::FillPortion(2))
+    container(card_rows)
+        .padding(15.0)
         .height(Length::Fill)
-        .vertical_alignment(iced::alignment::Vertical::Center);
-    elements_row = elements_row.push(class);
+        .into()
+}
+
+/// Cover of the card, or its name when the cover has not been downloaded, with its quantity and
+/// a button to flip it to its other form when it has one
+fn card_tile<'a>(
+    collection_card: &CollectionCard,
+    on_flip: Option<Message>,
+    covers_directory: &Path,
+) -> Element<'a, Message> {
+    let card = &collection_card.card;
+    let cover: Element<'a, Message> = match displayed_cover(covers_directory, &card.id) {
+        Some(cover_path) => image(cover_path)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+        None => container(text(card.name()).size(14.0))
+            .padding(10.0)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into(),
+    };
+    let badge = (collection_card.quantity > 0).then(|| collection_card.quantity.to_string());
+    let mut legend = row![text(card.id.clone()).size(12.0).width(Length::Fill)]
+        .align_items(iced::Alignment::Center);
+    if let Some(on_flip) = on_flip {
+        legend = legend.push(
+            button(text("Flip").size(12.0))
+                .padding([2.0, 6.0])
+                .on_press(on_flip),
+        );
+    }
+
+    column![
+        CardTile::new(cover)
+            .owned(collection_card.is_owned)
+            .badge(badge)
+            .width(TILE_WIDTH)
+            .height(TILE_HEIGHT),
+        legend,
+    ]
+    .spacing(5.0)
+    .width(Length::Fixed(TILE_WIDTH))
+    .into()
+}
 
-    let quantity_input = text_input("", &quantity)
-        .id(text_input::Id::new(card.id.clone()))
-        .on_input(|new_text| {
-            let card_clone = card.clone();
-            tracing::info!("{:?}", card_clone);
-            Message::UpdateQuantity(card_clone.id, new_text)
-        });
+fn table_row<'a>(
+    columns: &[Column],
+    collection_card: &'a CollectionCard,
+    playable_copies: u32,
+) -> TableRow<'a, Message, Theme, iced::Renderer> {
+    let card = &collection_card.card;
+    let mut elements_row = Row::new().padding([0.0, 10.0]);
 
-    let actions_row = row![quantity_input].width(Length::Fixed(100.0));
-    elements_row = elements_row.push(actions_row);
+    for column in columns {
+        let cell: Element<'a, Message> = match column.name.as_str() {
+            "Owned" => {
+                let owned_graphic = if collection_card.is_owned {
+                    Svg::new("resources/done.svg")
+                } else {
+                    Svg::new("resources/close.svg")
+                };
+                owned_graphic
+                    .width(column.width)
+                    .height(Length::Fill)
+                    .into()
+            }
+            "Rarity" => text_cell(card.rarity.to_string(), column.width),
+            "Playable" => text_cell(playable_copies.to_string(), column.width),
+            "Number" => text_cell(card.id.clone(), column.width),
+            "Name" => text_cell(card.name(), column.width),
+            "Class" => container(class_badge(card.card_class))
+                .width(column.width)
+                .height(Length::Fill)
+                .center_y()
+                .into(),
+            "Cost" => text_cell(card.cost.to_string(), column.width),
+            "Other form" => text_cell(
+                card.evolution.clone().unwrap_or_else(|| String::from("-")),
+                column.width,
+            ),
+            "Quantity" => Stepper::new(collection_card.quantity, |quantity| {
+                Message::UpdateQuantity(card.id.clone(), quantity)
+            })
+            .id(quantity_stepper_id(card.id.clone()))
+            .on_submit(Message::CommitQuantity(card.id.clone()))
+            .on_focus(Message::QuantityFocused(card.id.clone()))
+            .on_blur(Message::QuantityBlurred(card.id.clone()))
+            .width(column.width)
+            .into(),
+            "Wanted" => Stepper::new(collection_card.wanted, |wanted| {
+                Message::UpdateWanted(card.id.clone(), wanted)
+            })
+            .width(column.width)
+            .into(),
+            "Trade" => Stepper::new(collection_card.for_trade, |for_trade| {
+                Message::UpdateForTrade(card.id.clone(), for_trade)
+            })
+            .width(column.width)
+            .into(),
+            _ => continue,
+        };
+        elements_row = elements_row.push(cell);
+    }
 
     TableRow::new(elements_row.align_items(iced::Alignment::Center)).row_height(35.0)
 }
+
+fn text_cell<'a>(content: String, width: Length) -> Element<'a, Message> {
+    text(content)
+        .width(width)
+        .height(Length::Fill)
+        .vertical_alignment(iced::alignment::Vertical::Center)
+        .into()
+}
+
+fn quantity_stepper_id(card_id: String) -> stepper::Id {
+    stepper::Id::new(card_id)
+}
@@ -0,0 +1,175 @@
+use data::{
+    collection::CollectionCard,
+    config::Config,
+    db::get_extensions,
+    trade::{parse_friend_collection, propose_trade, ProposedTrade, TradeEntry},
+};
+use iced::{
+    widget::{button, column, container, row, scrollable, text, text_input},
+    Command, Length,
+};
+
+use crate::widget::Element;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PathChanged(String),
+    Load,
+    CopyToClipboard,
+}
+
+/// Compare the collection with the one exported by a friend to propose a trade
+pub struct Compare {
+    path: String,
+    error: Option<String>,
+    friend_cards: Vec<CollectionCard>,
+    proposed_trade: Option<ProposedTrade>,
+}
+
+impl Compare {
+    pub fn new() -> Self {
+        Self {
+            path: String::new(),
+            error: None,
+            friend_cards: Vec::new(),
+            proposed_trade: None,
+        }
+    }
+
+    pub fn update(&mut self, config: &Config, message: Message) -> Command<Message> {
+        match message {
+            Message::PathChanged(path) => {
+                self.path = path;
+
+                Command::none()
+            }
+            Message::Load => {
+                self.error = None;
+                self.proposed_trade = None;
+
+                let text = match std::fs::read_to_string(self.path.trim()) {
+                    Ok(text) => text,
+                    Err(error) => {
+                        self.error = Some(format!("Could not read {}: {}", self.path, error));
+                        return Command::none();
+                    }
+                };
+
+                let settings = config.progression_settings();
+                let our_cards: Vec<CollectionCard> = get_extensions(config)
+                    .into_iter()
+                    .flat_map(|progression| progression.extension_cards)
+                    .collect();
+
+                match parse_friend_collection(&text, &our_cards, &settings.target) {
+                    Ok(friend_cards) => {
+                        self.proposed_trade =
+                            Some(propose_trade(&our_cards, &friend_cards, &settings));
+                        self.friend_cards = friend_cards;
+                    }
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+
+                Command::none()
+            }
+            Message::CopyToClipboard => {
+                let Some(proposed_trade) = &self.proposed_trade else {
+                    return Command::none();
+                };
+
+                iced::clipboard::write(proposed_trade_text(proposed_trade))
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let file_input = row![
+            text_input("Path of the file exported by your friend", &self.path)
+                .on_input(Message::PathChanged)
+                .on_submit(Message::Load),
+            button(text("Load")).on_press(Message::Load),
+        ]
+        .spacing(10.0)
+        .align_items(iced::Alignment::Center);
+
+        let content: Element<'_, Message> = match (&self.error, &self.proposed_trade) {
+            (Some(error), _) => text(error).into(),
+            (None, Some(proposed_trade)) => {
+                let summary = row![
+                    text(format!(
+                        "{} cards in the collection of your friend",
+                        self.friend_cards.len()
+                    ))
+                    .width(Length::Fill),
+                    button(text("Copy the proposal")).on_press(Message::CopyToClipboard),
+                ]
+                .align_items(iced::Alignment::Center);
+
+                let entries = row![
+                    trade_entries("You give", &proposed_trade.given),
+                    trade_entries("You receive", &proposed_trade.received),
+                ]
+                .spacing(20.0)
+                .height(Length::Fill);
+
+                column![summary, entries].spacing(20.0).into()
+            }
+            (None, None) => {
+                text("Load the collection or the trade list exported by a friend").into()
+            }
+        };
+
+        container(column![file_input, content].spacing(20.0).max_width(900.0))
+            .padding(30.0)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .into()
+    }
+}
+
+fn trade_entries<'a>(title: &str, entries: &'a [TradeEntry]) -> Element<'a, Message> {
+    let lines: Vec<Element<'a, Message>> = entries
+        .iter()
+        .map(|entry| {
+            text(format!(
+                "{}x {} {} ({})",
+                entry.copies,
+                entry.card.id,
+                entry.card.name(),
+                entry.card.rarity
+            ))
+            .size(14.0)
+            .into()
+        })
+        .collect();
+
+    column![
+        text(title.to_string()).size(24.0),
+        scrollable(column(lines).spacing(5.0).padding([0.0, 15.0, 0.0, 0.0]))
+    ]
+    .spacing(10.0)
+    .width(Length::FillPortion(1))
+    .into()
+}
+
+fn proposed_trade_text(proposed_trade: &ProposedTrade) -> String {
+    let mut text = String::new();
+    for (title, entries) in [
+        ("I give", &proposed_trade.given),
+        ("I receive", &proposed_trade.received),
+    ] {
+        text.push_str(&format!("== {} ==\n", title));
+        for entry in entries {
+            text.push_str(&format!(
+                "{}x {} {}\n",
+                entry.copies,
+                entry.card.id,
+                entry.card.name()
+            ));
+        }
+        text.push('\n');
+    }
+
+    text
+}
@@ -0,0 +1,113 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use data::stats::{CollectionStats, RecentExtension};
+use iced::{
+    widget::{column, container, row, scrollable, text},
+    Length,
+};
+
+use crate::{
+    widget::Element,
+    widgets::stats::{class_completion_section, completion_section, total_tile},
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {}
+
+/// Summary of the whole collection
+pub struct Dashboard {
+    stats: CollectionStats,
+}
+
+impl Dashboard {
+    pub fn new(stats: CollectionStats) -> Self {
+        Self { stats }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let stats = &self.stats;
+        let (owned_variants, variants) = stats.collectible_variants();
+
+        let totals = row![
+            total_tile(
+                "Unique cards",
+                format!("{} / {}", stats.unique_cards, stats.total_cards)
+            ),
+            total_tile("Total copies", stats.total_copies.to_string()),
+            total_tile("Duplicates", stats.duplicates.to_string()),
+        ]
+        .spacing(15.0);
+
+        let printings = row![
+            total_tile(
+                "Playable cards",
+                format!("{} / {}", stats.owned_playable_cards, stats.playable_cards)
+            ),
+            total_tile(
+                "Collectible variants",
+                format!("{} / {}", owned_variants, variants)
+            ),
+        ]
+        .spacing(15.0);
+
+        let completions = row![
+            class_completion_section("By class", &stats.by_class),
+            completion_section("By rarity", &stats.by_rarity),
+            completion_section("By type", &stats.by_type),
+        ]
+        .spacing(30.0);
+
+        let content = column![
+            totals,
+            printings,
+            completions,
+            recent_extensions(&stats.recent_extensions)
+        ]
+        .spacing(30.0)
+        .padding([0.0, 15.0])
+        .max_width(900.0);
+
+        container(scrollable(content))
+            .padding([30.0, 0.0])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .into()
+    }
+}
+
+fn recent_extensions<'a>(extensions: &[RecentExtension]) -> Element<'a, Message> {
+    let mut section = column![text("Recently updated extensions").size(20.0)].spacing(10.0);
+    if extensions.is_empty() {
+        section = section.push(text("No card has been updated yet").size(14.0));
+    }
+
+    for recent_extension in extensions {
+        section = section.push(row![
+            text(format!(
+                "{} - {}",
+                recent_extension.extension.id, recent_extension.extension.name
+            ))
+            .width(Length::Fill),
+            text(elapsed_time(recent_extension.updated_at)).size(14.0),
+        ]);
+    }
+
+    section.into()
+}
+
+/// Human readable time elapsed since the given unix timestamp
+fn elapsed_time(timestamp: i64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default();
+    let elapsed = (now - timestamp).max(0);
+
+    match elapsed {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} minutes ago", elapsed / 60),
+        3600..=86399 => format!("{} hours ago", elapsed / 3600),
+        _ => format!("{} days ago", elapsed / 86400),
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+
+use data::{
+    collection::{CollectionCard, ExtensionProgression, ProgressionMode, ProgressionSettings},
+    config::Config,
+    covers::displayed_cover,
+    db::{get_extension, mark_cards_owned},
+};
+use iced::{
+    widget::{button, column, container, image, row, scrollable, text, Row},
+    Command, Length,
+};
+
+use crate::{
+    widget::Element,
+    widgets::stats::{class_completion_section, completion_section, total_tile},
+};
+
+/// Number of covers on each line of the missing cards grid
+const COVERS_PER_ROW: usize = 6;
+const COVER_WIDTH: f32 = 130.0;
+const COVER_HEIGHT: f32 = 182.0;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToCardsList(ExtensionProgression),
+    MarkAllOwned,
+    CopyNeededCards,
+}
+
+/// Overview of the completion of an extension
+pub struct ExtensionDetails {
+    extension_progression: ExtensionProgression,
+    settings: ProgressionSettings,
+    covers_directory: PathBuf,
+}
+
+impl ExtensionDetails {
+    pub fn new(
+        extension_progression: ExtensionProgression,
+        settings: ProgressionSettings,
+        covers_directory: PathBuf,
+    ) -> Self {
+        Self {
+            extension_progression,
+            settings,
+            covers_directory,
+        }
+    }
+
+    pub fn update(&mut self, config: &Config, message: Message) -> Command<Message> {
+        match message {
+            Message::ToCardsList(_) => Command::none(),
+            Message::MarkAllOwned => {
+                let missing_cards: Vec<String> = self
+                    .extension_progression
+                    .missing_cards(self.settings.special_cards)
+                    .iter()
+                    .map(|extension_card| extension_card.card.id.clone())
+                    .collect();
+                if let Err(error) = mark_cards_owned(config, &missing_cards) {
+                    tracing::error!("Could not mark the cards as owned: {}", error);
+                }
+
+                self.extension_progression =
+                    get_extension(config, &self.extension_progression.extension.id);
+                Command::none()
+            }
+            Message::CopyNeededCards => iced::clipboard::write(
+                self.extension_progression
+                    .needed_cards_to_text(&self.settings),
+            ),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let progression = &self.extension_progression;
+        let extension = &progression.extension;
+
+        let actions = row![
+            text(format!("{} - {}", extension.id, extension.name))
+                .size(24.0)
+                .width(Length::Fill),
+            button(text("Cards list")).on_press(Message::ToCardsList(progression.clone())),
+            button(text("Mark all as owned")).on_press(Message::MarkAllOwned),
+            button(text("Copy needed cards")).on_press(Message::CopyNeededCards),
+        ]
+        .spacing(10.0)
+        .align_items(iced::Alignment::Center);
+
+        let missing_cards = progression.missing_cards(self.settings.special_cards);
+        let (completed, total) = progression.completion(&self.settings);
+        let (playsets, cards) = progression.completion(&ProgressionSettings {
+            mode: ProgressionMode::CompletePlaysets,
+            ..self.settings
+        });
+
+        let totals = row![
+            total_tile(
+                &self.settings.mode.to_string(),
+                format!("{} / {}", completed, total)
+            ),
+            total_tile("Playsets done", format!("{} / {}", playsets, cards)),
+            total_tile("Missing cards", missing_cards.len().to_string()),
+        ]
+        .spacing(15.0);
+
+        let completions = row![
+            class_completion_section(
+                "By class",
+                &progression.completion_by(&self.settings, |card| card.card_class)
+            ),
+            completion_section(
+                "By rarity",
+                &progression.completion_by(&self.settings, |card| card.rarity.clone())
+            ),
+        ]
+        .spacing(30.0);
+
+        let content = column![
+            actions,
+            totals,
+            completions,
+            missing_cards_grid(&missing_cards, &self.covers_directory)
+        ]
+        .spacing(30.0)
+        .padding([0.0, 15.0])
+        .max_width(900.0);
+
+        container(scrollable(content))
+            .padding([30.0, 0.0])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .into()
+    }
+}
+
+fn missing_cards_grid<'a>(
+    missing_cards: &[&CollectionCard],
+    covers_directory: &Path,
+) -> Element<'a, Message> {
+    let mut section = column![text("Missing cards").size(20.0)].spacing(10.0);
+    if missing_cards.is_empty() {
+        section = section.push(text("Every card of the extension is owned").size(14.0));
+    }
+
+    for cards in missing_cards.chunks(COVERS_PER_ROW) {
+        let covers: Vec<Element<'a, Message>> = cards
+            .iter()
+            .map(|extension_card| card_cover(extension_card, covers_directory))
+            .collect();
+        section = section.push(Row::with_children(covers).spacing(10.0));
+    }
+
+    section.into()
+}
+
+/// Cover of the card, or its name when the cover has not been downloaded
+fn card_cover<'a>(
+    extension_card: &CollectionCard,
+    covers_directory: &Path,
+) -> Element<'a, Message> {
+    let card = &extension_card.card;
+    if let Some(cover_path) = displayed_cover(covers_directory, &card.id) {
+        return image(cover_path)
+            .width(Length::Fixed(COVER_WIDTH))
+            .height(Length::Fixed(COVER_HEIGHT))
+            .into();
+    }
+
+    container(
+        column![
+            text(card.id.clone()).size(12.0),
+            text(card.name()).size(14.0)
+        ]
+        .spacing(5.0)
+        .align_items(iced::Alignment::Center),
+    )
+    .padding(10.0)
+    .width(Length::Fixed(COVER_WIDTH))
+    .height(Length::Fixed(COVER_HEIGHT))
+    .center_x()
+    .center_y()
+    .style(crate::theme::Container::Tile)
+    .into()
+}
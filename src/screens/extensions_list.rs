@@ -1,71 +1,278 @@
-use data::collection::ExtensionProgression;
+use data::{
+    cards::SetType,
+    collection::{
+        ExtensionProgression, NeededCard, ProgressionMode, ProgressionSettings, SpecialCardsDisplay,
+    },
+    config::Config,
+    sort::{ExtensionsListSettings, ExtensionsSort},
+};
 use iced::{
-    widget::{button, column, container, progress_bar, row, scrollable, text},
-    Length,
+    widget::{button, column, container, progress_bar, row, scrollable, text, Row},
+    Command, Length,
 };
+use widgets::{stepper::Stepper, table_row::TableRow, virtual_list::VirtualList};
 
-use crate::widget::Element;
+use crate::{theme::Theme, widget::Element, widgets::chip::chip};
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ToDetails(ExtensionProgression),
+    SelectMode(ProgressionMode),
+    SelectSpecialCards(SpecialCardsDisplay),
+    UpdatePlaysetCopies(u8),
+    UpdateEvolvedCopies(u8),
+    ToggleNeededCards,
+    SortExtensions(ExtensionsSort),
+    ToggleGroupBySetType,
 }
 
 pub struct ExtensionsList {
     extensions_progress: Vec<ExtensionProgression>,
+    settings: ProgressionSettings,
+    list_settings: ExtensionsListSettings,
+    needed_cards: Vec<NeededCard>,
+    show_needed_cards: bool,
 }
 
 impl ExtensionsList {
-    pub fn new(progressions: Vec<ExtensionProgression>) -> Self {
-        Self {
+    pub fn new(
+        mut progressions: Vec<ExtensionProgression>,
+        settings: ProgressionSettings,
+        list_settings: ExtensionsListSettings,
+    ) -> Self {
+        list_settings.sort.sort(&mut progressions);
+
+        let mut extensions_list = Self {
             extensions_progress: progressions,
+            settings,
+            list_settings,
+            needed_cards: Vec::new(),
+            show_needed_cards: false,
+        };
+        extensions_list.compute_needed_cards();
+        extensions_list
+    }
+
+    pub fn update(&mut self, config: &Config, message: Message) -> Command<Message> {
+        match message {
+            Message::ToDetails(_) => return Command::none(),
+            Message::SelectMode(mode) => self.settings.mode = mode,
+            Message::SelectSpecialCards(special_cards) => {
+                self.settings.special_cards = special_cards
+            }
+            Message::UpdatePlaysetCopies(copies) => self.settings.target.copies = copies,
+            Message::UpdateEvolvedCopies(copies) => self.settings.target.evolved_copies = copies,
+            Message::ToggleNeededCards => {
+                self.show_needed_cards = !self.show_needed_cards;
+                return Command::none();
+            }
+            Message::SortExtensions(sort) => {
+                self.list_settings.sort = sort;
+                sort.sort(&mut self.extensions_progress);
+                self.save_list_settings(config);
+                return Command::none();
+            }
+            Message::ToggleGroupBySetType => {
+                self.list_settings.group_by_set_type = !self.list_settings.group_by_set_type;
+                self.save_list_settings(config);
+                return Command::none();
+            }
         }
+
+        self.compute_needed_cards();
+        if let Err(error) = config.save_progression_settings(self.settings) {
+            tracing::error!("Could not save the progression settings: {}", error);
+        }
+
+        Command::none()
     }
 
-    pub fn view<'a>(&self) -> Element<'a, Message> {
-        let extensions_widgets: Vec<Element<'a, Message>> = self
-            .extensions_progress
-            .clone()
-            .into_iter()
-            .map(|progress| extension_progress(&progress))
-            .collect();
+    pub fn view(&self) -> Element<'_, Message> {
+        let content = if self.show_needed_cards {
+            needed_cards_list(&self.needed_cards)
+        } else {
+            let extensions_widgets: Vec<Element<'_, Message>> =
+                if self.list_settings.group_by_set_type {
+                    SetType::ALL
+                        .iter()
+                        .flat_map(|set_type| self.set_type_group(*set_type))
+                        .collect()
+                } else {
+                    self.extensions_progress
+                        .iter()
+                        .map(|progress| extension_progress(progress, &self.settings))
+                        .collect()
+                };
 
-        container(scrollable(
-            column(extensions_widgets)
-                .spacing(15.0)
-                .padding([0.0, 15.0])
+            scrollable(
+                column(extensions_widgets)
+                    .spacing(15.0)
+                    .padding([0.0, 15.0]),
+            )
+            .height(Length::Fill)
+            .into()
+        };
+
+        container(
+            column![self.settings_view(), content]
+                .spacing(20.0)
                 .max_width(900.0),
-        ))
+        )
         .padding([30.0, 0.0])
         .width(Length::Fill)
         .height(Length::Fill)
         .align_x(iced::alignment::Horizontal::Center)
         .into()
     }
+
+    fn settings_view(&self) -> Element<'_, Message> {
+        let modes: Vec<Element<'_, Message>> = ProgressionMode::ALL
+            .iter()
+            .map(|mode| {
+                chip(
+                    mode.to_string(),
+                    self.settings.mode == *mode,
+                    Message::SelectMode(*mode),
+                )
+            })
+            .collect();
+
+        let special_cards: Vec<Element<'_, Message>> = SpecialCardsDisplay::ALL
+            .iter()
+            .map(|special_cards| {
+                chip(
+                    special_cards.to_string(),
+                    self.settings.special_cards == *special_cards,
+                    Message::SelectSpecialCards(*special_cards),
+                )
+            })
+            .collect();
+
+        let mut sorts: Vec<Element<'_, Message>> = ExtensionsSort::ALL
+            .iter()
+            .map(|sort| {
+                chip(
+                    sort.to_string(),
+                    self.list_settings.sort == *sort,
+                    Message::SortExtensions(*sort),
+                )
+            })
+            .collect();
+        sorts.push(chip(
+            String::from("Group by set type"),
+            self.list_settings.group_by_set_type,
+            Message::ToggleGroupBySetType,
+        ));
+
+        let target = row![
+            text("Copies per card"),
+            Stepper::new(self.settings.target.copies, Message::UpdatePlaysetCopies)
+                .min(1)
+                .width(Length::Fixed(110.0)),
+            text("Evolved"),
+            Stepper::new(
+                self.settings.target.evolved_copies,
+                Message::UpdateEvolvedCopies
+            )
+            .width(Length::Fixed(110.0)),
+            chip(
+                format!("Cards still needed ({})", self.needed_cards.len()),
+                self.show_needed_cards,
+                Message::ToggleNeededCards,
+            ),
+        ]
+        .spacing(10.0)
+        .align_items(iced::Alignment::Center);
+
+        column![
+            Row::with_children(modes).spacing(5.0),
+            Row::with_children(special_cards).spacing(5.0),
+            target,
+            Row::with_children(sorts).spacing(5.0)
+        ]
+        .spacing(10.0)
+        .padding([0.0, 15.0])
+        .into()
+    }
+
+    /// Title of the set type followed by its extensions, nothing when it has no extension
+    fn set_type_group(&self, set_type: SetType) -> Vec<Element<'_, Message>> {
+        let extensions: Vec<Element<'_, Message>> = self
+            .extensions_progress
+            .iter()
+            .filter(|progress| progress.extension.set_type == set_type)
+            .map(|progress| extension_progress(progress, &self.settings))
+            .collect();
+        if extensions.is_empty() {
+            return extensions;
+        }
+
+        std::iter::once(text(set_type.to_string()).size(20.0).into())
+            .chain(extensions)
+            .collect()
+    }
+
+    fn save_list_settings(&self, config: &Config) {
+        if let Err(error) = config.save_extensions_list_settings(self.list_settings) {
+            tracing::error!("Could not save the extensions list settings: {}", error);
+        }
+    }
+
+    fn compute_needed_cards(&mut self) {
+        self.needed_cards = self
+            .extensions_progress
+            .iter()
+            .flat_map(|progress| progress.needed_cards(&self.settings))
+            .collect();
+    }
 }
 
-fn extension_progress<'a>(progress: &ExtensionProgression) -> Element<'a, Message> {
-    let label_text = text(format!(
-        "{} - {}",
-        progress.extension.id,
-        progress.extension.name.clone()
-    ))
-    .width(Length::FillPortion(4))
-    .height(Length::Fill)
-    .vertical_alignment(iced::alignment::Vertical::Center);
+fn extension_progress<'a>(
+    progress: &ExtensionProgression,
+    settings: &ProgressionSettings,
+) -> Element<'a, Message> {
+    let extension = &progress.extension;
+    let mut details = format!(
+        "{} - {} - {} cards",
+        extension.set_type,
+        extension
+            .release_date
+            .as_deref()
+            .unwrap_or("Release date unknown"),
+        extension.card_count
+    );
+    let special_cards_count = progress.special_cards().len();
+    if settings.special_cards == SpecialCardsDisplay::Separated && special_cards_count > 0 {
+        details.push_str(&format!(" - {} tokens and leaders", special_cards_count));
+    }
+
+    let label = column![
+        text(format!("{} - {}", extension.id, extension.name.clone())),
+        text(details)
+            .size(12.0)
+            .style(crate::theme::Text::Secondary),
+    ];
+
+    let label_text = container(label)
+        .width(Length::FillPortion(4))
+        .height(Length::Fill)
+        .center_y();
+
+    let (completed, total) = progress.completion(settings);
+    let progression = progress.progression_by(settings);
 
     let progress_text = text(format!(
         "{} / {} ({:.2}%)",
-        progress.owned_cards().len(),
-        progress.cards().len(),
-        progress.progression() * 100.0
+        completed,
+        total,
+        progression * 100.0
     ))
     .width(Length::FillPortion(1))
     .height(Length::Fill)
     .horizontal_alignment(iced::alignment::Horizontal::Right)
     .vertical_alignment(iced::alignment::Vertical::Center);
 
-    let progress_bar = progress_bar(0.0..=1.0, progress.progression())
+    let progress_bar = progress_bar(0.0..=1.0, progression)
         .height(Length::Fixed(15.0))
         .width(Length::FillPortion(3));
 
@@ -81,3 +288,47 @@ fn extension_progress<'a>(progress: &ExtensionProgression) -> Element<'a, Messag
         .on_press(Message::ToDetails(progress.clone()))
         .into()
 }
+
+fn needed_cards_list(needed_cards: &[NeededCard]) -> Element<'_, Message> {
+    let rows = VirtualList::new(needed_cards.len(), move |index| {
+        needed_card_row(&needed_cards[index]).into()
+    })
+    .row_key(move |index| &needed_cards[index].card.id)
+    .row_height(35.0)
+    .spacing(6.0);
+
+    container(rows)
+        .padding([0.0, 15.0])
+        .height(Length::Fill)
+        .into()
+}
+
+fn needed_card_row(needed_card: &NeededCard) -> TableRow<'_, Message, Theme, iced::Renderer> {
+    let card = &needed_card.card;
+
+    let cells = row![
+        cell(card.extension.name.clone(), Length::FillPortion(3)),
+        cell(card.id.clone(), Length::FillPortion(2)),
+        cell(card.name(), Length::FillPortion(4)),
+        cell(
+            format!("{} owned", needed_card.quantity),
+            Length::FillPortion(1)
+        ),
+        cell(
+            format!("{} missing", needed_card.missing_copies),
+            Length::FillPortion(1)
+        ),
+    ]
+    .padding([0.0, 10.0])
+    .align_items(iced::Alignment::Center);
+
+    TableRow::new(cells).row_height(35.0)
+}
+
+fn cell<'a>(content: String, width: Length) -> Element<'a, Message> {
+    text(content)
+        .width(width)
+        .height(Length::Fill)
+        .vertical_alignment(iced::alignment::Vertical::Center)
+        .into()
+}
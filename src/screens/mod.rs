@@ -1,3 +1,9 @@
 pub mod cards_list;
+pub mod compare;
+pub mod dashboard;
+pub mod extension_details;
 pub mod extensions_list;
+pub mod search;
+pub mod settings;
+pub mod trades;
 pub mod update;
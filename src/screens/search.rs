@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use data::{
+    collection::CollectionCard,
+    config::{ColumnLayout, Config},
+    db::{save_screen_sort, search_cards, update_card_quantity},
+    sort::CardsSort,
+};
+use iced::{
+    widget::{column, container, text, text_input, Row, Svg},
+    Command, Length,
+};
+use widgets::{header::Column, stepper::Stepper, table_row::TableRow, virtual_list::VirtualList};
+
+use crate::{
+    blocking::spawn_blocking,
+    theme::Theme,
+    widget::Element,
+    widgets::table_header::{
+        apply_layout, column_sort_field, columns_layout, move_column, resize_column, table_header,
+    },
+};
+
+/// Key used to remember the sort and the columns of the table
+const TABLE_KEY: &str = "search";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Search(String),
+    /// Result of the search started with the given number
+    CardsFound(u64, Result<Vec<CollectionCard>, String>),
+    UpdateQuantity(String, u8),
+    SortBy(usize),
+    ResizeColumn(usize, f32),
+    MoveColumn(usize, usize),
+    SaveColumns,
+}
+
+pub struct Search {
+    columns: Vec<Column>,
+    search: String,
+    /// Number of the last search started, the results of the previous ones are ignored
+    search_number: u64,
+    found_cards: Vec<CollectionCard>,
+    sort: Option<CardsSort>,
+}
+
+impl Search {
+    pub fn new(sort: Option<CardsSort>, columns_layout: &[ColumnLayout]) -> Self {
+        Self {
+            columns: apply_layout(
+                vec![
+                    Column::new("Owned")
+                        .width(Length::FillPortion(1))
+                        .sortable(false),
+                    Column::new("Number").width(Length::FillPortion(2)),
+                    Column::new("Name").width(Length::FillPortion(4)),
+                    Column::new("Type").width(Length::FillPortion(2)),
+                    Column::new("Extension").width(Length::FillPortion(3)),
+                    Column::new("Quantity").width(Length::Fixed(120.0)),
+                ],
+                columns_layout,
+            ),
+            search: String::new(),
+            search_number: 0,
+            found_cards: Vec::new(),
+            sort,
+        }
+    }
+
+    pub fn update(&mut self, config: &Arc<Config>, message: Message) -> Command<Message> {
+        match message {
+            Message::Search(search) => {
+                self.search_number += 1;
+                self.search = search.clone();
+
+                // The query runs outside of the interface, the input stays responsive while typing
+                let search_number = self.search_number;
+                let config = config.clone();
+                Command::perform(
+                    spawn_blocking(move || {
+                        search_cards(&config, &search).map_err(|error| error.to_string())
+                    }),
+                    move |found_cards| Message::CardsFound(search_number, found_cards),
+                )
+            }
+            Message::CardsFound(search_number, found_cards) => {
+                if search_number != self.search_number {
+                    return Command::none();
+                }
+
+                match found_cards {
+                    Ok(found_cards) => self.found_cards = found_cards,
+                    Err(error) => tracing::error!("{}", error),
+                }
+                self.sort_found_cards();
+
+                Command::none()
+            }
+            Message::UpdateQuantity(card_id, quantity) => {
+                if let Err(error) = update_card_quantity(config, &card_id, quantity) {
+                    tracing::error!("Could not update the quantity of {}: {}", card_id, error);
+                    return Command::none();
+                }
+
+                if let Some(found_card) = self
+                    .found_cards
+                    .iter_mut()
+                    .find(|found_card| found_card.card.id == card_id)
+                {
+                    found_card.quantity = quantity;
+                    found_card.is_owned = quantity > 0;
+                }
+
+                Command::none()
+            }
+            Message::SortBy(column_index) => {
+                let Some(field) = self.columns.get(column_index).and_then(column_sort_field) else {
+                    return Command::none();
+                };
+
+                self.sort = CardsSort::next(self.sort, field);
+                if let Err(error) = save_screen_sort(config, TABLE_KEY, self.sort.as_ref()) {
+                    tracing::error!("Could not save the sort of the search: {}", error);
+                }
+
+                if self.sort.is_none() {
+                    // Without any sort, the cards are displayed by relevance again
+                    return self.update(config, Message::Search(self.search.clone()));
+                }
+                self.sort_found_cards();
+
+                Command::none()
+            }
+            Message::ResizeColumn(column_index, width) => {
+                resize_column(&mut self.columns, column_index, width);
+
+                Command::none()
+            }
+            Message::MoveColumn(from, to) => {
+                move_column(&mut self.columns, from, to);
+                self.save_columns(config);
+
+                Command::none()
+            }
+            Message::SaveColumns => {
+                self.save_columns(config);
+
+                Command::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let search_input = text_input("Search a name, trait, type or effect", &self.search)
+            .on_input(Message::Search)
+            .width(Length::Fill);
+
+        let results_count = text(format!("{} cards found", self.found_cards.len()));
+
+        let filters = column![search_input, results_count]
+            .spacing(10.0)
+            .padding(15.0)
+            .into();
+
+        container(column(vec![
+            filters,
+            found_cards_list(&self.columns, self.sort, &self.found_cards),
+        ]))
+        .max_width(900.0)
+        .into()
+    }
+
+    fn save_columns(&self, config: &Config) {
+        if let Err(error) = config.save_table_layout(TABLE_KEY, columns_layout(&self.columns)) {
+            tracing::error!("Could not save the columns of the search: {}", error);
+        }
+    }
+
+    fn sort_found_cards(&mut self) {
+        if let Some(sort) = &self.sort {
+            sort.sort(&mut self.found_cards);
+        }
+    }
+}
+
+fn found_cards_list<'a>(
+    columns: &'a [Column],
+    sort: Option<CardsSort>,
+    found_cards: &'a [CollectionCard],
+) -> Element<'a, Message> {
+    let headers = table_header(
+        columns,
+        sort,
+        Message::SortBy,
+        Message::ResizeColumn,
+        Message::SaveColumns,
+        Message::MoveColumn,
+    );
+
+    let card_rows = VirtualList::new(found_cards.len(), move |index| {
+        found_card_row(columns, &found_cards[index]).into()
+    })
+    .row_key(move |index| &found_cards[index].card.id)
+    .row_height(35.0)
+    .spacing(6.0);
+
+    column(vec![headers, card_rows.into()])
+        .spacing(10.0)
+        .padding(15.0)
+        .height(Length::Fill)
+        .into()
+}
+
+fn found_card_row<'a>(
+    columns: &[Column],
+    collection_card: &'a CollectionCard,
+) -> TableRow<'a, Message, Theme, iced::Renderer> {
+    let card = &collection_card.card;
+
+    let mut cells = Row::new().padding([0.0, 10.0]);
+    for column in columns {
+        let content = match column.name.as_str() {
+            "Owned" => {
+                let owned_graphic = if collection_card.is_owned {
+                    Svg::new("resources/done.svg")
+                } else {
+                    Svg::new("resources/close.svg")
+                };
+                cells = cells.push(owned_graphic.width(column.width).height(Length::Fill));
+                continue;
+            }
+            "Number" => card.id.clone(),
+            "Name" => card.name(),
+            "Type" => card.card_type.to_string(),
+            "Extension" => card.extension.name.clone(),
+            "Quantity" => {
+                cells = cells.push(
+                    Stepper::new(collection_card.quantity, |quantity| {
+                        Message::UpdateQuantity(card.id.clone(), quantity)
+                    })
+                    .width(column.width),
+                );
+                continue;
+            }
+            _ => continue,
+        };
+        cells = cells.push(cell(content, column.width));
+    }
+
+    TableRow::new(cells.align_items(iced::Alignment::Center)).row_height(35.0)
+}
+
+fn cell<'a>(content: String, width: Length) -> Element<'a, Message> {
+    text(content)
+        .width(width)
+        .height(Length::Fill)
+        .vertical_alignment(iced::alignment::Vertical::Center)
+        .into()
+}
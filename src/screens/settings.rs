@@ -0,0 +1,319 @@
+use std::path::PathBuf;
+
+use data::{
+    config::{Config, Language, SerializedConfiguration},
+    covers::{clear_image_cache, sync_image_cache, CacheUsage, ImageCacheStats, ImageKind},
+};
+use iced::{
+    widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input},
+    Alignment, Command, Length,
+};
+
+use crate::{
+    theme::{self, Theme},
+    widget::Element,
+    widgets::stats::total_tile,
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    UpdateSourceUrl(String),
+    SelectTheme(String),
+    SelectLanguage(Language),
+    ToggleSyncOnStartup(bool),
+    ToggleDownloadCovers(bool),
+    Save,
+    SyncNow,
+    UpdateDataDirectory(String),
+    UpdateCacheDirectory(String),
+    MoveData,
+    ClearThumbnails,
+    ClearImages,
+}
+
+pub struct Settings {
+    configuration: SerializedConfiguration,
+    themes: Vec<String>,
+    directories: Vec<(&'static str, String)>,
+    portable: bool,
+    data_directory: String,
+    cache_directory: String,
+    moved: Option<Result<(), String>>,
+    saved: Option<Result<(), String>>,
+    image_cache: ImageCacheStats,
+    error: Option<String>,
+}
+
+impl Settings {
+    pub fn new(
+        mut configuration: SerializedConfiguration,
+        themes: Vec<String>,
+        config: &Config,
+        image_cache: ImageCacheStats,
+    ) -> Self {
+        let directories = vec![
+            ("Configuration", config.config_file.display().to_string()),
+            ("Database", config.db_file.display().to_string()),
+            ("Covers", config.covers_directory.display().to_string()),
+            ("Logs", config.log_file.display().to_string()),
+        ];
+
+        // Show the name of the theme when the configuration uses an alias
+        if let Some(theme) = Theme::named(&configuration.theme, &[]) {
+            configuration.theme = theme.name().to_string();
+        }
+
+        Self {
+            configuration,
+            themes,
+            directories,
+            portable: config.portable,
+            data_directory: config.data_directory.display().to_string(),
+            cache_directory: config.cache_directory.display().to_string(),
+            moved: None,
+            saved: None,
+            image_cache,
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, config: &Config, message: Message) -> Command<Message> {
+        let kind = match message {
+            Message::UpdateSourceUrl(url) => {
+                self.configuration.shadowverse_api_url = url;
+                self.saved = None;
+                return Command::none();
+            }
+            Message::SelectTheme(theme) => {
+                self.configuration.theme = theme;
+                self.saved = None;
+                return Command::none();
+            }
+            Message::SelectLanguage(language) => {
+                self.configuration.language = language;
+                self.saved = None;
+                return Command::none();
+            }
+            Message::ToggleSyncOnStartup(on_startup) => {
+                self.configuration.sync.on_startup = on_startup;
+                self.saved = None;
+                return Command::none();
+            }
+            Message::ToggleDownloadCovers(download_covers) => {
+                self.configuration.sync.download_covers = download_covers;
+                self.saved = None;
+                return Command::none();
+            }
+            Message::Save => {
+                self.configuration.shadowverse_api_url =
+                    self.configuration.shadowverse_api_url.trim().to_string();
+                self.saved = Some(
+                    config
+                        .save_configuration(&self.configuration)
+                        .map_err(|error| error.to_string()),
+                );
+                return Command::none();
+            }
+            Message::UpdateDataDirectory(directory) => {
+                self.data_directory = directory;
+                return Command::none();
+            }
+            Message::UpdateCacheDirectory(directory) => {
+                self.cache_directory = directory;
+                return Command::none();
+            }
+            // Handled by the application
+            Message::SyncNow | Message::MoveData => return Command::none(),
+            Message::ClearThumbnails => Some(ImageKind::Thumbnail),
+            Message::ClearImages => None,
+        };
+
+        self.error = clear_image_cache(config, kind)
+            .and_then(|_| sync_image_cache(config))
+            .map(|image_cache| self.image_cache = image_cache)
+            .err()
+            .map(|error| error.to_string());
+
+        Command::none()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let content = column![
+            self.general_section(),
+            self.sync_section(),
+            self.directories_section(),
+            self.image_cache_section()
+        ]
+        .spacing(30.0)
+        .padding([0.0, 15.0])
+        .max_width(900.0);
+
+        container(scrollable(content))
+            .padding([30.0, 0.0])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .into()
+    }
+
+    fn general_section(&self) -> Element<'_, Message> {
+        let theme = self
+            .themes
+            .iter()
+            .find(|theme| **theme == self.configuration.theme)
+            .cloned();
+
+        let mut section = column![
+            text("General").size(20.0),
+            setting_row(
+                "Source website",
+                text_input(
+                    "https://en.shadowverse-evolve.com",
+                    &self.configuration.shadowverse_api_url
+                )
+                .on_input(Message::UpdateSourceUrl)
+                .on_submit(Message::Save)
+                .into()
+            ),
+            setting_row(
+                "Theme",
+                pick_list(self.themes.as_slice(), theme, Message::SelectTheme).into()
+            ),
+            setting_row(
+                "Language",
+                pick_list(
+                    &Language::ALL[..],
+                    Some(self.configuration.language),
+                    Message::SelectLanguage
+                )
+                .into()
+            ),
+            button(text("Save")).on_press(Message::Save),
+        ]
+        .spacing(15.0);
+
+        match &self.saved {
+            Some(Ok(())) => section = section.push(text("The configuration has been saved")),
+            Some(Err(error)) => section = section.push(text(error)),
+            None => {}
+        }
+
+        section.into()
+    }
+
+    fn sync_section(&self) -> Element<'_, Message> {
+        column![
+            text("Synchronization").size(20.0),
+            checkbox(
+                "Look for new cards on startup",
+                self.configuration.sync.on_startup
+            )
+            .on_toggle(Message::ToggleSyncOnStartup),
+            checkbox(
+                "Download the covers of the new cards",
+                self.configuration.sync.download_covers
+            )
+            .on_toggle(Message::ToggleDownloadCovers),
+            button(text("Look for new cards now")).on_press(Message::SyncNow),
+        ]
+        .spacing(15.0)
+        .into()
+    }
+
+    /// Directories the data are moved to, relative directories are relative to the configuration
+    /// file
+    pub fn target_directories(&self) -> (PathBuf, PathBuf) {
+        (
+            PathBuf::from(self.data_directory.trim()),
+            PathBuf::from(self.cache_directory.trim()),
+        )
+    }
+
+    /// The last save of the configuration succeeded
+    pub fn is_saved(&self) -> bool {
+        matches!(self.saved, Some(Ok(())))
+    }
+
+    pub fn data_moved(&mut self, result: Result<(), String>) {
+        self.moved = Some(result);
+    }
+
+    fn directories_section(&self) -> Element<'_, Message> {
+        let mut section = self.directories.iter().fold(
+            column![text("Data directories").size(20.0)].spacing(10.0),
+            |section, (label, path)| {
+                section.push(setting_row(
+                    label,
+                    text(path).style(theme::Text::Secondary).into(),
+                ))
+            },
+        );
+
+        if self.portable {
+            section = section.push(
+                text("Portable mode, the files are kept next to the executable")
+                    .style(theme::Text::Secondary),
+            );
+        }
+
+        section = section
+            .push(setting_row(
+                "Data directory",
+                text_input("Database and logs", &self.data_directory)
+                    .on_input(Message::UpdateDataDirectory)
+                    .into(),
+            ))
+            .push(setting_row(
+                "Cache directory",
+                text_input("Covers", &self.cache_directory)
+                    .on_input(Message::UpdateCacheDirectory)
+                    .into(),
+            ))
+            .push(button(text("Move the data")).on_press(Message::MoveData));
+
+        match &self.moved {
+            Some(Ok(())) => section = section.push(text(
+                "The data have been moved, restart the application to write the logs in the new directory",
+            )),
+            Some(Err(error)) => section = section.push(text(error)),
+            None => {}
+        }
+
+        section.into()
+    }
+
+    fn image_cache_section(&self) -> Element<'_, Message> {
+        let usage = row![
+            cache_tile("Covers", &self.image_cache.covers),
+            cache_tile("Thumbnails", &self.image_cache.thumbnails),
+        ]
+        .spacing(15.0);
+
+        let actions = row![
+            button(text("Delete the thumbnails")).on_press(Message::ClearThumbnails),
+            button(text("Delete every image")).on_press(Message::ClearImages),
+        ]
+        .spacing(10.0);
+
+        let mut image_cache = column![text("Image cache").size(20.0), usage, actions].spacing(15.0);
+        if let Some(error) = &self.error {
+            image_cache = image_cache.push(text(error));
+        }
+
+        image_cache.into()
+    }
+}
+
+fn setting_row<'a>(label: &str, input: Element<'a, Message>) -> Element<'a, Message> {
+    row![
+        text(label).width(Length::FillPortion(1)),
+        container(input).width(Length::FillPortion(3))
+    ]
+    .spacing(15.0)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+fn cache_tile<'a>(label: &str, usage: &CacheUsage) -> Element<'a, Message> {
+    total_tile(label, format!("{} files - {}", usage.files, usage.size()))
+}
@@ -0,0 +1,94 @@
+use data::{
+    collection::{CollectionCard, ExtensionProgression, ProgressionSettings},
+    trade::{collection_to_text, group_by_extension, TradeEntry, TradeList},
+};
+use iced::{
+    widget::{button, column, container, row, scrollable, text},
+    Command, Length,
+};
+
+use crate::widget::Element;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    CopyToClipboard,
+    CopyCollection,
+}
+
+pub struct Trades {
+    trade_list: TradeList,
+    collection: Vec<CollectionCard>,
+}
+
+impl Trades {
+    pub fn new(progressions: Vec<ExtensionProgression>, settings: &ProgressionSettings) -> Self {
+        Self {
+            trade_list: TradeList::from_collection(&progressions, settings),
+            collection: progressions
+                .into_iter()
+                .flat_map(|progression| progression.extension_cards)
+                .collect(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::CopyToClipboard => iced::clipboard::write(self.trade_list.to_text()),
+            Message::CopyCollection => iced::clipboard::write(collection_to_text(&self.collection)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let actions = row![
+            text(format!(
+                "{} cards wanted, {} cards to trade",
+                self.trade_list.wants.len(),
+                self.trade_list.tradeables.len()
+            ))
+            .width(Length::Fill),
+            button(text("Copy as text")).on_press(Message::CopyToClipboard),
+            button(text("Copy collection")).on_press(Message::CopyCollection),
+        ]
+        .spacing(10.0)
+        .align_items(iced::Alignment::Center);
+
+        let lists = row![
+            trade_entries("Wants", &self.trade_list.wants),
+            trade_entries("Trades", &self.trade_list.tradeables),
+        ]
+        .spacing(20.0)
+        .height(Length::Fill);
+
+        container(column![actions, lists].spacing(20.0).max_width(900.0))
+            .padding(30.0)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .into()
+    }
+}
+
+fn trade_entries<'a>(title: &str, entries: &'a [TradeEntry]) -> Element<'a, Message> {
+    let mut groups = column![].spacing(15.0).padding([0.0, 15.0, 0.0, 0.0]);
+    for (extension, entries) in group_by_extension(entries) {
+        let mut lines: Vec<Element<'a, Message>> =
+            vec![text(format!("{} - {}", extension.id, extension.name)).into()];
+        lines.extend(entries.into_iter().map(|entry| {
+            text(format!(
+                "{}x {} {}",
+                entry.copies,
+                entry.card.id,
+                entry.card.name()
+            ))
+            .size(14.0)
+            .into()
+        }));
+
+        groups = groups.push(column(lines).spacing(5.0));
+    }
+
+    column![text(title.to_string()).size(24.0), scrollable(groups)]
+        .spacing(10.0)
+        .width(Length::FillPortion(1))
+        .into()
+}
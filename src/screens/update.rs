@@ -1,7 +1,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use cards_updater::{get_cards, get_max_page, get_number_of_cards};
-use data::{cards::Card, config::Config, db};
+use data::{cards::Card, config::Config, covers, db, release_date::ReleaseDate};
 use iced::{
     futures::SinkExt,
     subscription,
@@ -9,7 +9,7 @@ use iced::{
     Command, Length,
 };
 
-use crate::widget::Element;
+use crate::{blocking::spawn_blocking, widget::Element};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -22,6 +22,8 @@ pub struct CardsUpdater {
     current_card_name: String,
 
     total_cards: u32,
+    /// Release dates of the configuration, read once when the sync starts
+    release_dates: Vec<ReleaseDate>,
 
     step: DownloadStep,
 }
@@ -33,6 +35,7 @@ impl CardsUpdater {
             current_card_name: "".to_string(),
 
             total_cards: 0,
+            release_dates: Vec::new(),
 
             step: DownloadStep::Metadatas,
         }
@@ -47,18 +50,26 @@ impl CardsUpdater {
             Message::CardFetched(event) => match event {
                 Event::MetadatasList(total_cards) => {
                     self.total_cards = total_cards;
+                    self.release_dates = config.release_dates();
                     self.step = DownloadStep::Card;
                 }
                 Event::IncreaseDownloadedCounter(increment) => {
                     self.current_card_index += increment;
                 }
                 Event::Card(card) => {
-                    let _ = db::upsert_card(config, card.clone());
+                    let _ = db::upsert_card(config, *card.clone(), &self.release_dates);
+                    if let Err(error) = covers::track_card_images(config, &card.id) {
+                        tracing::error!("{}", error);
+                    }
 
                     self.current_card_index += 1;
                     self.current_card_name = card.name();
                 }
-                Event::Finished => {}
+                Event::Finished => {
+                    if let Err(error) = db::link_evolutions(config) {
+                        tracing::error!("Could not link the evolved cards: {}", error);
+                    }
+                }
                 Event::Error(error) => {
                     tracing::error!("{:?}", error);
                 }
@@ -116,7 +127,7 @@ enum DownloadStep {
 pub enum Event {
     MetadatasList(u32),
     IncreaseDownloadedCounter(usize),
-    Card(Card),
+    Card(Box<Card>),
     Error(cards_updater::ErrorKind),
     Finished,
 }
@@ -128,7 +139,10 @@ fn fetch_single_card(config: Arc<Config>) -> iced::Subscription<Event> {
         std::any::TypeId::of::<DownloadCardsTask>(),
         1,
         move |mut output| async move {
-            let Ok(number_of_cards) = get_number_of_cards().await else {
+            let source_url = config.source_url();
+            let sync_settings = config.sync_settings();
+
+            let Ok(number_of_cards) = get_number_of_cards(&source_url).await else {
                 let _ = output
                     .send(Event::Error(cards_updater::ErrorKind::NumberOfCardsError))
                     .await;
@@ -137,10 +151,11 @@ fn fetch_single_card(config: Arc<Config>) -> iced::Subscription<Event> {
 
             let _ = output.send(Event::MetadatasList(number_of_cards)).await;
 
-            let max_page = get_max_page().await;
+            let max_page = get_max_page(&source_url).await;
+            let extension_mappings = config.extension_mappings();
 
             for page_number in 1..=max_page {
-                let cards = get_cards(page_number).await.unwrap();
+                let cards = get_cards(&source_url, page_number).await.unwrap();
                 let cards_to_download = exclude_already_downloaded(cards.clone(), &config);
 
                 let _ = output
@@ -149,11 +164,38 @@ fn fetch_single_card(config: Arc<Config>) -> iced::Subscription<Event> {
                     ))
                     .await;
 
-                let cards_iter = cards_to_download.iter();
-                for current_card in cards_iter {
-                    match cards_updater::download_card(current_card, &config.covers_directory) {
-                        Ok(card) => {
-                            let _ = output.send(Event::Card(card)).await;
+                for current_card in cards_to_download {
+                    // The requests block, they are made on their own thread
+                    let source_url = source_url.clone();
+                    let extension_mappings = extension_mappings.clone();
+                    let covers_directory = config.covers_directory.clone();
+                    let download = spawn_blocking(move || {
+                        let card = cards_updater::download_card(
+                            &source_url,
+                            &current_card,
+                            &extension_mappings,
+                        )?;
+                        // A card without its cover is still saved
+                        let cover = if sync_settings.download_covers {
+                            cards_updater::download_cover(
+                                &source_url,
+                                &card.id,
+                                &card.extension.id,
+                                &covers_directory,
+                            )
+                        } else {
+                            Ok(())
+                        };
+
+                        Ok((card, cover.err()))
+                    });
+
+                    match download.await {
+                        Ok((card, cover_error)) => {
+                            if let Some(error) = cover_error {
+                                let _ = output.send(Event::Error(error)).await;
+                            }
+                            let _ = output.send(Event::Card(Box::new(card))).await;
                         }
                         Err(error) => {
                             let _ = output.send(Event::Error(error)).await;
@@ -0,0 +1,57 @@
+use iced::{
+    widget::{button, column, container, text},
+    window, Application, Command, Length,
+};
+
+use crate::{theme, widget::Element};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Quit,
+}
+
+/// Window displayed instead of the application when it can not start
+pub struct StartupError {
+    error: String,
+}
+
+impl Application for StartupError {
+    type Executor = iced::executor::Default;
+    type Message = Message;
+    type Theme = theme::Theme;
+    type Flags = String;
+
+    fn new(error: Self::Flags) -> (Self, Command<Self::Message>) {
+        (Self { error }, Command::none())
+    }
+
+    fn title(&self) -> String {
+        "Shadowverse utils".into()
+    }
+
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        match message {
+            Message::Quit => window::close(window::Id::MAIN),
+        }
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        let content = column![
+            text("The application could not start").size(24.0),
+            text(&self.error),
+            text("Fix the problem above, then start the application again")
+                .style(theme::Text::Secondary),
+            button(text("Quit")).on_press(Message::Quit),
+        ]
+        .spacing(20.0)
+        .max_width(700.0);
+
+        container(content)
+            .padding(30.0)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+}
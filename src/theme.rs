@@ -1,18 +1,32 @@
+use data::{
+    cards::CardClass,
+    config::Config,
+    themes::{load_user_themes, parse_color, ThemeColors, ThemeDefinition},
+};
 use iced::{color, Color};
 
 mod application;
 mod button;
+mod card_tile;
+mod checkbox;
 mod combo_box;
 mod container;
+mod header;
+mod pick_list;
 mod progress_bar;
 mod scrollable;
+mod stepper;
 mod svg;
 mod table_row;
 mod text;
 mod text_input;
+mod virtual_list;
 
 pub use button::Button;
 pub use container::Container;
+pub use progress_bar::ProgressBar;
+pub use svg::Svg;
+pub use text::Text;
 
 #[derive(Debug, Clone)]
 struct Palette {
@@ -24,26 +38,277 @@ struct Palette {
     background_light: Color,
     primary_text: Color,
     secondary_text: Color,
+    classes: ClassColors,
+}
+
+impl Palette {
+    const DARK: Palette = Palette {
+        primary: color!(0x00, 0xbc, 0xd4),
+        dark_primary: color!(0x00, 0x97, 0xa7),
+        light_primary: color!(0xb2, 0xeb, 0xf2),
+        accent: color!(0x00, 0x74, 0x91),
+        background: color!(0x27, 0x29, 0x2d),
+        background_light: color!(0x3a, 0x3d, 0x42),
+        primary_text: color!(0xf9, 0xf9, 0xf9),
+        secondary_text: color!(0x75, 0x75, 0x75),
+        classes: ClassColors::BRIGHT,
+    };
+
+    const LIGHT: Palette = Palette {
+        primary: color!(0x00, 0x97, 0xa7),
+        dark_primary: color!(0x00, 0x83, 0x8f),
+        light_primary: color!(0x00, 0x60, 0x64),
+        accent: color!(0x00, 0x74, 0x91),
+        background: color!(0xf5, 0xf5, 0xf5),
+        background_light: color!(0xe0, 0xe3, 0xe7),
+        primary_text: color!(0x21, 0x21, 0x21),
+        secondary_text: color!(0x61, 0x61, 0x61),
+        classes: ClassColors::DEEP,
+    };
+
+    const HIGH_CONTRAST: Palette = Palette {
+        primary: color!(0xff, 0xeb, 0x3b),
+        dark_primary: color!(0xfb, 0xc0, 0x2d),
+        light_primary: color!(0xff, 0xff, 0xff),
+        accent: color!(0x00, 0xe5, 0xff),
+        background: color!(0x00, 0x00, 0x00),
+        background_light: color!(0x1f, 0x1f, 0x1f),
+        primary_text: color!(0xff, 0xff, 0xff),
+        secondary_text: color!(0xd0, 0xd0, 0xd0),
+        classes: ClassColors::BRIGHT,
+    };
+
+    /// Dark palette using the colors of a class
+    const fn class_accent(
+        primary: Color,
+        dark_primary: Color,
+        light_primary: Color,
+        accent: Color,
+    ) -> Palette {
+        Palette {
+            primary,
+            dark_primary,
+            light_primary,
+            accent,
+            ..Palette::DARK
+        }
+    }
+
+    /// Replace the colors set in a theme file
+    fn with_colors(mut self, colors: &ThemeColors) -> Palette {
+        let overrides = [
+            (&mut self.primary, &colors.primary),
+            (&mut self.dark_primary, &colors.dark_primary),
+            (&mut self.light_primary, &colors.light_primary),
+            (&mut self.accent, &colors.accent),
+            (&mut self.background, &colors.background),
+            (&mut self.background_light, &colors.background_light),
+            (&mut self.primary_text, &colors.primary_text),
+            (&mut self.secondary_text, &colors.secondary_text),
+        ];
+
+        for (color, value) in overrides {
+            if let Some([r, g, b]) = value.as_deref().and_then(parse_color) {
+                *color = Color::from_rgb8(r, g, b);
+            }
+        }
+
+        self
+    }
+}
+
+/// Color of each class, used to recognize the class of a card at a glance
+#[derive(Debug, Clone)]
+struct ClassColors {
+    forestcraft: Color,
+    swordcraft: Color,
+    runecraft: Color,
+    dragoncraft: Color,
+    abysscraft: Color,
+    havencraft: Color,
+    neutral: Color,
+}
+
+impl ClassColors {
+    /// Colors readable on a dark background
+    const BRIGHT: ClassColors = ClassColors {
+        forestcraft: color!(0x66, 0xbb, 0x6a),
+        swordcraft: color!(0xfd, 0xd8, 0x35),
+        runecraft: color!(0x64, 0xb5, 0xf6),
+        dragoncraft: color!(0xff, 0x8a, 0x65),
+        abysscraft: color!(0xba, 0x68, 0xc8),
+        havencraft: color!(0xe6, 0xd7, 0xa3),
+        neutral: color!(0xbd, 0xbd, 0xbd),
+    };
+
+    /// Colors readable on a light background
+    const DEEP: ClassColors = ClassColors {
+        forestcraft: color!(0x2e, 0x7d, 0x32),
+        swordcraft: color!(0xb2, 0x8b, 0x00),
+        runecraft: color!(0x15, 0x65, 0xc0),
+        dragoncraft: color!(0xd8, 0x43, 0x15),
+        abysscraft: color!(0x7b, 0x1f, 0xa2),
+        havencraft: color!(0x9e, 0x8a, 0x3a),
+        neutral: color!(0x61, 0x61, 0x61),
+    };
+
+    fn get(&self, class: CardClass) -> Color {
+        match class {
+            CardClass::Forestcraft => self.forestcraft,
+            CardClass::Swordcraft => self.swordcraft,
+            CardClass::Runecraft => self.runecraft,
+            CardClass::Dragoncraft => self.dragoncraft,
+            CardClass::Abysscraft => self.abysscraft,
+            CardClass::Havencraft => self.havencraft,
+            CardClass::Neutral => self.neutral,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Theme {
+    name: String,
     palette: Palette,
 }
 
+impl Theme {
+    /// Names of the themes bundled with the application
+    pub const BUILT_IN: [&'static str; 9] = [
+        "dark",
+        "light",
+        "high-contrast",
+        "forestcraft",
+        "swordcraft",
+        "runecraft",
+        "dragoncraft",
+        "abysscraft",
+        "havencraft",
+    ];
+
+    fn built_in(name: &str) -> Option<Theme> {
+        let palette = match name {
+            // Name of the dark theme in the first configuration files
+            "dark" | "default" => Palette::DARK,
+            "light" => Palette::LIGHT,
+            "high-contrast" => Palette::HIGH_CONTRAST,
+            "forestcraft" => Palette::class_accent(
+                color!(0x4c, 0xaf, 0x50),
+                color!(0x38, 0x8e, 0x3c),
+                color!(0xc8, 0xe6, 0xc9),
+                color!(0x2e, 0x7d, 0x32),
+            ),
+            "swordcraft" => Palette::class_accent(
+                color!(0xfb, 0xc0, 0x2d),
+                color!(0xf9, 0xa8, 0x25),
+                color!(0xff, 0xf9, 0xc4),
+                color!(0xf5, 0x7f, 0x17),
+            ),
+            "runecraft" => Palette::class_accent(
+                color!(0x42, 0x8b, 0xe0),
+                color!(0x1e, 0x6f, 0xc0),
+                color!(0xbb, 0xde, 0xfb),
+                color!(0x15, 0x65, 0xc0),
+            ),
+            "dragoncraft" => Palette::class_accent(
+                color!(0xff, 0x70, 0x43),
+                color!(0xe6, 0x4a, 0x19),
+                color!(0xff, 0xcc, 0xbc),
+                color!(0xbf, 0x36, 0x0c),
+            ),
+            "abysscraft" => Palette::class_accent(
+                color!(0xab, 0x47, 0xbc),
+                color!(0x8e, 0x24, 0xaa),
+                color!(0xe1, 0xbe, 0xe7),
+                color!(0x6a, 0x1b, 0x9a),
+            ),
+            "havencraft" => Palette::class_accent(
+                color!(0xd4, 0xc2, 0x7a),
+                color!(0xb8, 0xa4, 0x4f),
+                color!(0xf5, 0xef, 0xd6),
+                color!(0x9e, 0x8a, 0x3a),
+            ),
+            _ => return None,
+        };
+
+        Some(Theme {
+            name: if name == "default" { "dark" } else { name }.to_string(),
+            palette,
+        })
+    }
+
+    /// Find a bundled theme or a theme of the user by its name
+    pub fn named(name: &str, user_themes: &[ThemeDefinition]) -> Option<Theme> {
+        if let Some(theme) = Self::built_in(name) {
+            return Some(theme);
+        }
+
+        let definition = user_themes.iter().find(|theme| theme.name == name)?;
+        let base = definition
+            .base
+            .as_deref()
+            .and_then(Self::built_in)
+            .unwrap_or_default();
+
+        Some(Theme {
+            name: definition.name.clone(),
+            palette: base.palette.with_colors(&definition.colors),
+        })
+    }
+
+    /// Names of the bundled themes followed by the themes of the user
+    pub fn names(user_themes: &[ThemeDefinition]) -> Vec<String> {
+        Self::BUILT_IN
+            .iter()
+            .map(|name| name.to_string())
+            .chain(
+                user_themes
+                    .iter()
+                    .map(|theme| theme.name.clone())
+                    .filter(|name| Self::built_in(name).is_none()),
+            )
+            .collect()
+    }
+
+    /// Theme selected in the configuration, the dark theme is used when it does not exist
+    pub fn from_config(config: &Config) -> Theme {
+        let configuration = config.configuration();
+
+        Self::named(&configuration.theme, &load_user_themes(config)).unwrap_or_else(|| {
+            tracing::warn!(
+                "The theme {} does not exist, the default theme is used",
+                configuration.theme
+            );
+            Theme::default()
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn class_color(&self, class: CardClass) -> Color {
+        self.palette.classes.get(class)
+    }
+}
+
+/// Icon of a class, colored with the color of the class by the `Svg::Class` style
+pub fn class_icon(class: CardClass) -> &'static str {
+    match class {
+        CardClass::Forestcraft => "resources/classes/forestcraft.svg",
+        CardClass::Swordcraft => "resources/classes/swordcraft.svg",
+        CardClass::Runecraft => "resources/classes/runecraft.svg",
+        CardClass::Dragoncraft => "resources/classes/dragoncraft.svg",
+        CardClass::Abysscraft => "resources/classes/abysscraft.svg",
+        CardClass::Havencraft => "resources/classes/havencraft.svg",
+        CardClass::Neutral => "resources/classes/neutral.svg",
+    }
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Self {
-            palette: Palette {
-                primary: color!(0x00, 0xbc, 0xd4),
-                dark_primary: color!(0x00, 0x97, 0xa7),
-                light_primary: color!(0xb2, 0xeb, 0xf2),
-                accent: color!(0x00, 0x74, 0x91),
-                background: color!(0x27, 0x29, 0x2d),
-                background_light: color!(0x3a, 0x3d, 0x42),
-                primary_text: color!(0xf9, 0xf9, 0xf9),
-                secondary_text: color!(0x75, 0x75, 0x75),
-            },
+            name: String::from("dark"),
+            palette: Palette::DARK,
         }
     }
 }
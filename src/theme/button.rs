@@ -8,6 +8,8 @@ pub enum Button {
     Primary,
     Extension,
     Sidebar,
+    FilterChip,
+    FilterChipSelected,
 }
 
 impl button::StyleSheet for Theme {
@@ -40,6 +42,25 @@ impl button::StyleSheet for Theme {
                 },
                 ..Default::default()
             },
+            Button::FilterChip => button::Appearance {
+                text_color: self.palette.secondary_text,
+                border: Border {
+                    color: self.palette.secondary_text,
+                    width: 1.0,
+                    radius: 12.0.into(),
+                },
+                ..Default::default()
+            },
+            Button::FilterChipSelected => button::Appearance {
+                background: Some(self.palette.accent.into()),
+                text_color: self.palette.primary_text,
+                border: Border {
+                    color: self.palette.primary,
+                    width: 1.0,
+                    radius: 12.0.into(),
+                },
+                ..Default::default()
+            },
         }
     }
 
@@ -59,6 +80,14 @@ impl button::StyleSheet for Theme {
                 border: Border::default(),
                 ..Default::default()
             },
+            Button::FilterChip => button::Appearance {
+                text_color: self.palette.light_primary,
+                border: Border {
+                    color: self.palette.light_primary,
+                    ..active_style.border
+                },
+                ..active_style
+            },
             _ => self.active(style),
         }
     }
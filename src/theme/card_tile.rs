@@ -0,0 +1,35 @@
+use iced::{Border, Color};
+use widgets::card_tile;
+
+use super::Theme;
+
+impl card_tile::style::Stylesheet for Theme {
+    type Style = ();
+
+    fn owned(&self) -> card_tile::style::Appearance {
+        card_tile::style::Appearance {
+            border: Border {
+                color: self.palette.primary,
+                width: 2.0,
+                radius: 5.0.into(),
+            },
+            overlay: None,
+            badge_background: iced::Background::Color(self.palette.primary),
+            badge_text_color: self.palette.primary_text,
+        }
+    }
+
+    fn missing(&self) -> card_tile::style::Appearance {
+        card_tile::style::Appearance {
+            border: Border {
+                color: self.palette.background_light,
+                ..self.owned().border
+            },
+            overlay: Some(iced::Background::Color(Color {
+                a: 0.6,
+                ..self.palette.background
+            })),
+            ..self.owned()
+        }
+    }
+}
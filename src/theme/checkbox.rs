@@ -0,0 +1,44 @@
+use iced::{widget::checkbox, Border};
+
+use super::Theme;
+
+impl checkbox::StyleSheet for Theme {
+    type Style = ();
+
+    fn active(&self, _style: &Self::Style, is_checked: bool) -> checkbox::Appearance {
+        let background = if is_checked {
+            self.palette.primary
+        } else {
+            self.palette.background_light
+        };
+
+        checkbox::Appearance {
+            background: iced::Background::Color(background),
+            icon_color: self.palette.primary_text,
+            border: Border {
+                radius: 4.0.into(),
+                width: 1.0,
+                color: self.palette.primary,
+            },
+            text_color: Some(self.palette.primary_text),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style, is_checked: bool) -> checkbox::Appearance {
+        checkbox::Appearance {
+            border: Border {
+                color: self.palette.light_primary,
+                ..self.active(style, is_checked).border
+            },
+            ..self.active(style, is_checked)
+        }
+    }
+
+    fn disabled(&self, style: &Self::Style, is_checked: bool) -> checkbox::Appearance {
+        checkbox::Appearance {
+            icon_color: self.palette.secondary_text,
+            text_color: Some(self.palette.secondary_text),
+            ..self.active(style, is_checked)
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub enum Container {
     #[default]
     Default,
     Sidebar,
+    Tile,
 }
 
 impl container::StyleSheet for Theme {
@@ -16,7 +17,7 @@ impl container::StyleSheet for Theme {
         match style {
             Container::Default => container::Appearance::default(),
             Container::Sidebar => container::Appearance {
-                text_color: Color::WHITE.into(),
+                text_color: self.palette.primary_text.into(),
                 background: Some(self.palette.background_light.into()),
                 border: Border {
                     radius: 0.0.into(),
@@ -25,6 +26,16 @@ impl container::StyleSheet for Theme {
                 },
                 ..Default::default()
             },
+            Container::Tile => container::Appearance {
+                text_color: self.palette.primary_text.into(),
+                background: Some(self.palette.background_light.into()),
+                border: Border {
+                    radius: 10.0.into(),
+                    width: 0.0,
+                    color: Color::TRANSPARENT,
+                },
+                ..Default::default()
+            },
         }
     }
 }
@@ -0,0 +1,22 @@
+use iced::{Border, Color};
+use widgets::header;
+
+use super::Theme;
+
+impl header::style::Stylesheet for Theme {
+    type Style = ();
+
+    fn active(&self) -> header::style::Appearance {
+        header::style::Appearance {
+            background: iced::Background::Color(Color::TRANSPARENT),
+            border: Border {
+                color: Color::TRANSPARENT,
+                width: 0.0,
+                radius: 0.0.into(),
+            },
+            text_color: self.palette.primary_text,
+            sort_indicator: self.palette.primary,
+            drop_indicator: self.palette.light_primary,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use iced::{widget::pick_list, Border};
+
+use super::Theme;
+
+impl pick_list::StyleSheet for Theme {
+    type Style = ();
+
+    fn active(&self, _style: &Self::Style) -> pick_list::Appearance {
+        pick_list::Appearance {
+            text_color: self.palette.primary_text,
+            placeholder_color: self.palette.secondary_text,
+            handle_color: self.palette.primary_text,
+            background: iced::Background::Color(self.palette.background_light),
+            border: Border {
+                radius: 4.0.into(),
+                width: 1.0,
+                color: self.palette.background_light,
+            },
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> pick_list::Appearance {
+        pick_list::Appearance {
+            border: Border {
+                color: self.palette.primary,
+                ..self.active(style).border
+            },
+            ..self.active(style)
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use data::cards::CardClass;
 use iced::widget::progress_bar;
 
 use super::Theme;
@@ -6,15 +7,22 @@ use super::Theme;
 pub enum ProgressBar {
     #[default]
     Default,
+    /// Bar filled with the color of a class
+    Class(CardClass),
 }
 
 impl progress_bar::StyleSheet for Theme {
     type Style = ProgressBar;
 
-    fn appearance(&self, _style: &Self::Style) -> progress_bar::Appearance {
+    fn appearance(&self, style: &Self::Style) -> progress_bar::Appearance {
+        let bar = match style {
+            ProgressBar::Default => self.palette.primary,
+            ProgressBar::Class(class) => self.class_color(*class),
+        };
+
         progress_bar::Appearance {
             background: self.palette.light_primary.into(),
-            bar: self.palette.primary.into(),
+            bar: bar.into(),
             border_radius: 15.0.into(),
         }
     }
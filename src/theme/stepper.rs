@@ -0,0 +1,56 @@
+use iced::{color, Border};
+use widgets::stepper;
+
+use super::Theme;
+
+impl stepper::style::Stylesheet for Theme {
+    type Style = ();
+
+    fn active(&self) -> stepper::style::Appearance {
+        stepper::style::Appearance {
+            background: iced::Background::Color(self.palette.background),
+            border: Border {
+                radius: 4.0.into(),
+                width: 1.0,
+                color: self.palette.background_light,
+            },
+            text_color: self.palette.primary_text,
+            selection: self.palette.accent,
+            button_background: iced::Background::Color(self.palette.background_light),
+            hovered_button_background: iced::Background::Color(self.palette.dark_primary),
+            button_text_color: self.palette.primary_text,
+            disabled_button_text_color: self.palette.secondary_text,
+        }
+    }
+
+    fn hovered(&self) -> stepper::style::Appearance {
+        stepper::style::Appearance {
+            border: Border {
+                color: self.palette.dark_primary,
+                ..self.active().border
+            },
+            ..self.active()
+        }
+    }
+
+    fn focused(&self) -> stepper::style::Appearance {
+        stepper::style::Appearance {
+            border: Border {
+                color: self.palette.primary,
+                ..self.active().border
+            },
+            ..self.active()
+        }
+    }
+
+    fn invalid(&self) -> stepper::style::Appearance {
+        stepper::style::Appearance {
+            border: Border {
+                color: color!(0xe5, 0x39, 0x35),
+                ..self.active().border
+            },
+            text_color: color!(0xe5, 0x39, 0x35),
+            ..self.focused()
+        }
+    }
+}
@@ -1,19 +1,29 @@
-use iced::{widget::svg, Color};
+use data::cards::CardClass;
+use iced::widget::svg;
 
 use super::Theme;
 
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Svg {
+    #[default]
+    Default,
+    /// Icon drawn with the color of a class
+    Class(CardClass),
+}
+
 impl svg::StyleSheet for Theme {
-    type Style = ();
+    type Style = Svg;
+
+    fn appearance(&self, style: &Self::Style) -> svg::Appearance {
+        let color = match style {
+            Svg::Default => self.palette.primary_text,
+            Svg::Class(class) => self.class_color(*class),
+        };
 
-    fn appearance(&self, _style: &Self::Style) -> svg::Appearance {
-        svg::Appearance {
-            color: Some(Color::WHITE),
-        }
+        svg::Appearance { color: Some(color) }
     }
 
-    fn hovered(&self, _style: &Self::Style) -> svg::Appearance {
-        svg::Appearance {
-            color: Some(Color::WHITE),
-        }
+    fn hovered(&self, style: &Self::Style) -> svg::Appearance {
+        self.appearance(style)
     }
 }
@@ -1,3 +1,4 @@
+use data::cards::CardClass;
 use iced::widget::text;
 
 use super::Theme;
@@ -6,6 +7,9 @@ use super::Theme;
 pub enum Text {
     #[default]
     Default,
+    Secondary,
+    /// Color of a class
+    Class(CardClass),
 }
 
 impl text::StyleSheet for Theme {
@@ -16,6 +20,12 @@ impl text::StyleSheet for Theme {
             Text::Default => text::Appearance {
                 ..Default::default()
             },
+            Text::Secondary => text::Appearance {
+                color: Some(self.palette.secondary_text),
+            },
+            Text::Class(class) => text::Appearance {
+                color: Some(self.class_color(class)),
+            },
         }
     }
 }
@@ -0,0 +1,23 @@
+use iced::Color;
+use widgets::virtual_list;
+
+use super::Theme;
+
+impl virtual_list::style::Stylesheet for Theme {
+    type Style = ();
+
+    fn active(&self) -> virtual_list::style::Appearance {
+        virtual_list::style::Appearance {
+            scrollbar: iced::Background::Color(Color::TRANSPARENT),
+            scroller: iced::Background::Color(self.palette.primary),
+            radius: 5.0,
+        }
+    }
+
+    fn hovered(&self) -> virtual_list::style::Appearance {
+        virtual_list::style::Appearance {
+            scroller: iced::Background::Color(self.palette.dark_primary),
+            ..self.active()
+        }
+    }
+}
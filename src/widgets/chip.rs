@@ -0,0 +1,22 @@
+use iced::widget::{button, text};
+
+use crate::widget::Element;
+
+/// Small toggle button used to select a value among a few choices
+pub fn chip<'a, Message: Clone + 'a>(
+    label: String,
+    is_selected: bool,
+    message: Message,
+) -> Element<'a, Message> {
+    let style = if is_selected {
+        crate::theme::Button::FilterChipSelected
+    } else {
+        crate::theme::Button::FilterChip
+    };
+
+    button(text(label).size(14.0))
+        .padding([4.0, 10.0])
+        .style(style)
+        .on_press(message)
+        .into()
+}
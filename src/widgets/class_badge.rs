@@ -0,0 +1,52 @@
+use data::cards::CardClass;
+use iced::{
+    widget::{button, row, svg, text, tooltip},
+    Alignment, Length,
+};
+
+use crate::{theme, widget::Element};
+
+/// Icon and name of a class, drawn with the color of the class
+pub fn class_badge<'a, Message: 'a>(class: CardClass) -> Element<'a, Message> {
+    row![
+        class_icon(class, 16.0),
+        text(class.to_string()).style(theme::Text::Class(class)),
+    ]
+    .spacing(6.0)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+pub fn class_icon<'a, Message: 'a>(class: CardClass, size: f32) -> Element<'a, Message> {
+    svg(svg::Handle::from_path(theme::class_icon(class)))
+        .width(Length::Fixed(size))
+        .height(Length::Fixed(size))
+        .style(theme::Svg::Class(class))
+        .into()
+}
+
+/// Toggle button of a class filter, its icon is drawn with the color of the class and its name is
+/// shown on hover
+pub fn class_chip<'a, Message: Clone + 'a>(
+    class: CardClass,
+    is_selected: bool,
+    message: Message,
+) -> Element<'a, Message> {
+    let style = if is_selected {
+        theme::Button::FilterChipSelected
+    } else {
+        theme::Button::FilterChip
+    };
+
+    tooltip(
+        button(class_icon(class, 20.0))
+            .padding(6.0)
+            .style(style)
+            .on_press(message),
+        text(class.to_string()).style(theme::Text::Class(class)),
+        tooltip::Position::Bottom,
+    )
+    .padding(6.0)
+    .style(theme::Container::Tile)
+    .into()
+}
@@ -1 +1,5 @@
+pub mod chip;
+pub mod class_badge;
 pub mod sidebar;
+pub mod stats;
+pub mod table_header;
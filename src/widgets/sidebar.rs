@@ -10,8 +10,18 @@ use crate::{
 };
 
 pub fn sidebar<'a>() -> Container<'a, ApplicationMessage> {
-    let buttons_container =
-        container(column![sidebar_button("Progression", "progression"),]).height(Length::Fill);
+    let buttons_container = container(
+        column![
+            sidebar_button("Dashboard", "dashboard"),
+            sidebar_button("Progression", "progression"),
+            sidebar_button("Search", "search"),
+            sidebar_button("Trades", "trades"),
+            sidebar_button("Compare", "compare"),
+            sidebar_button("Settings", "settings"),
+        ]
+        .spacing(10.0),
+    )
+    .height(Length::Fill);
 
     container(buttons_container)
         .style(theme::Container::Sidebar)
@@ -0,0 +1,72 @@
+use data::{cards::CardClass, stats::CategoryCompletion};
+use iced::{
+    widget::{column, container, progress_bar, row, text},
+    Length,
+};
+
+use crate::{theme, widget::Element, widgets::class_badge::class_badge};
+
+/// Tile showing a single value of the statistics
+pub fn total_tile<'a, Message: 'a>(label: &str, value: String) -> Element<'a, Message> {
+    container(
+        column![text(label.to_string()).size(14.0), text(value).size(28.0)]
+            .spacing(5.0)
+            .align_items(iced::Alignment::Center),
+    )
+    .padding(15.0)
+    .width(Length::FillPortion(1))
+    .center_x()
+    .style(crate::theme::Container::Tile)
+    .into()
+}
+
+/// Progress bar of each category of a completion breakdown
+pub fn completion_section<'a, Message: 'a>(
+    title: &str,
+    completions: &[CategoryCompletion],
+) -> Element<'a, Message> {
+    completions_column(title, completions, |_| None)
+}
+
+/// Completion of each class, drawn with the icon and the color of the class
+pub fn class_completion_section<'a, Message: 'a>(
+    title: &str,
+    completions: &[CategoryCompletion],
+) -> Element<'a, Message> {
+    completions_column(title, completions, |name| {
+        Some(CardClass::from(name.to_string()))
+    })
+}
+
+fn completions_column<'a, Message: 'a>(
+    title: &str,
+    completions: &[CategoryCompletion],
+    class: impl Fn(&str) -> Option<CardClass>,
+) -> Element<'a, Message> {
+    let mut section = column![text(title.to_string()).size(20.0)].spacing(10.0);
+    for completion in completions {
+        let class = class(&completion.name);
+        let (label, bar_style) = match class {
+            Some(class) => (class_badge(class), theme::ProgressBar::Class(class)),
+            None => (
+                text(completion.name.clone()).into(),
+                theme::ProgressBar::Default,
+            ),
+        };
+
+        section = section.push(
+            column![
+                row![
+                    container(label).width(Length::Fill),
+                    text(format!("{} / {}", completion.owned, completion.total)).size(14.0),
+                ],
+                progress_bar(0.0..=1.0, completion.progression())
+                    .height(Length::Fixed(8.0))
+                    .style(bar_style),
+            ]
+            .spacing(4.0),
+        );
+    }
+
+    section.width(Length::FillPortion(1)).into()
+}
@@ -0,0 +1,116 @@
+use data::{
+    config::ColumnLayout,
+    sort::{CardsSort, SortField, SortOrder},
+};
+use iced::Length;
+use widgets::{
+    header::{self, Column, Header},
+    virtual_list::{SCROLLBAR_MARGIN, SCROLLBAR_WIDTH},
+};
+
+use crate::widget::Element;
+
+/// Header of a cards table, clicking on a column sorts the table using the field displayed in it
+/// while dragging a column resizes or moves it.
+///
+/// The header leaves room for the scrollbar of the virtual list displaying the rows so the
+/// columns stay aligned with the cells.
+pub fn table_header<'a, Message: Clone + 'a>(
+    columns: &[Column],
+    sort: Option<CardsSort>,
+    on_sort: fn(usize) -> Message,
+    on_resize: fn(usize, f32) -> Message,
+    on_resize_end: Message,
+    on_move: fn(usize, usize) -> Message,
+) -> Element<'a, Message> {
+    let sorted_column = sort.and_then(|sort| {
+        let index = columns
+            .iter()
+            .position(|column| column_sort_field(column) == Some(sort.field))?;
+        let order = match sort.order {
+            SortOrder::Ascending => header::SortOrder::Ascending,
+            SortOrder::Descending => header::SortOrder::Descending,
+        };
+        Some((index, order))
+    });
+
+    Header::new(columns)
+        .padding([0.0, 10.0 + SCROLLBAR_WIDTH + SCROLLBAR_MARGIN, 0.0, 10.0])
+        .sort(sorted_column)
+        .on_sort(on_sort)
+        .on_resize(on_resize)
+        .on_resize_end(on_resize_end)
+        .on_move(on_move)
+        .into()
+}
+
+pub fn column_sort_field(column: &Column) -> Option<SortField> {
+    if !column.sortable {
+        return None;
+    }
+
+    match column.name.as_str() {
+        "Number" => Some(SortField::Number),
+        "Name" => Some(SortField::Name),
+        "Rarity" => Some(SortField::Rarity),
+        "Class" => Some(SortField::Class),
+        "Type" => Some(SortField::Type),
+        "Cost" => Some(SortField::Cost),
+        "Extension" => Some(SortField::Extension),
+        "Quantity" => Some(SortField::Quantity),
+        _ => None,
+    }
+}
+
+/// Reorder and resize the default columns of a table using the layout saved by the user, the
+/// columns missing from the saved layout are kept at the end of the table
+pub fn apply_layout(default_columns: Vec<Column>, layout: &[ColumnLayout]) -> Vec<Column> {
+    let mut remaining_columns = default_columns;
+    let mut columns = Vec::with_capacity(remaining_columns.len());
+
+    for column_layout in layout {
+        let Some(position) = remaining_columns
+            .iter()
+            .position(|column| column.name == column_layout.name)
+        else {
+            continue;
+        };
+
+        let mut column = remaining_columns.remove(position);
+        if let Some(width) = column_layout.width {
+            column.width = Length::Fixed(width);
+        }
+        columns.push(column);
+    }
+
+    columns.append(&mut remaining_columns);
+    columns
+}
+
+pub fn columns_layout(columns: &[Column]) -> Vec<ColumnLayout> {
+    columns
+        .iter()
+        .map(|column| ColumnLayout {
+            name: column.name.clone(),
+            width: match column.width {
+                Length::Fixed(width) => Some(width),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+pub fn resize_column(columns: &mut [Column], index: usize, width: f32) {
+    if let Some(column) = columns.get_mut(index) {
+        column.width = Length::Fixed(width);
+    }
+}
+
+pub fn move_column(columns: &mut Vec<Column>, from: usize, to: usize) {
+    if from >= columns.len() || to >= columns.len() {
+        return;
+    }
+
+    let column = columns.remove(from);
+    columns.insert(to, column);
+}
@@ -0,0 +1,230 @@
+use iced_core::{
+    alignment, layout, mouse, renderer,
+    text::{self, LineHeight, Shaping},
+    widget::{Operation, Tree},
+    Border, Color, Element, Length, Pixels, Rectangle, Shadow, Size, Widget,
+};
+
+use self::style::Stylesheet;
+
+pub mod style;
+
+const BADGE_SIZE: f32 = 24.0;
+const BADGE_MARGIN: f32 = 4.0;
+
+/// Tile of a card in a gallery: its content, usually the cover, is dimmed when the card is not
+/// owned and a badge can be drawn in the top right corner, like the owned quantity.
+pub struct CardTile<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    is_owned: bool,
+    badge: Option<String>,
+    width: f32,
+    height: f32,
+}
+
+impl<'a, Message, Theme, Renderer> CardTile<'a, Message, Theme, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            is_owned: true,
+            badge: None,
+            width: 120.0,
+            height: 168.0,
+        }
+    }
+
+    pub fn owned(mut self, is_owned: bool) -> Self {
+        self.is_owned = is_owned;
+        self
+    }
+
+    pub fn badge(mut self, badge: Option<String>) -> Self {
+        self.badge = badge;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for CardTile<'a, Message, Theme, Renderer>
+where
+    Theme: Stylesheet,
+    Renderer: text::Renderer,
+{
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fixed(self.width), Length::Fixed(self.height))
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = Size::new(self.width, self.height);
+        let content = self.content.as_widget().layout(
+            &mut tree.children[0],
+            renderer,
+            &layout::Limits::new(Size::ZERO, size),
+        );
+
+        layout::Node::with_children(limits.resolve(self.width, self.height, size), vec![content])
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: iced_core::Event,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn iced_core::Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> iced_core::event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout.children().next().unwrap(),
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let appearance = if self.is_owned {
+            theme.owned()
+        } else {
+            theme.missing()
+        };
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+        );
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: appearance.border,
+                shadow: Shadow::default(),
+            },
+            appearance.overlay.unwrap_or(Color::TRANSPARENT.into()),
+        );
+
+        if let Some(badge) = &self.badge {
+            let badge_bounds = Rectangle {
+                x: bounds.x + bounds.width - BADGE_SIZE - BADGE_MARGIN,
+                y: bounds.y + BADGE_MARGIN,
+                width: BADGE_SIZE,
+                height: BADGE_SIZE,
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: badge_bounds,
+                    border: Border {
+                        radius: (BADGE_SIZE / 2.0).into(),
+                        ..Border::default()
+                    },
+                    shadow: Shadow::default(),
+                },
+                appearance.badge_background,
+            );
+            renderer.fill_text(
+                text::Text {
+                    content: badge,
+                    bounds: badge_bounds.size(),
+                    size: Pixels(14.0),
+                    line_height: LineHeight::default(),
+                    font: renderer.default_font(),
+                    horizontal_alignment: alignment::Horizontal::Center,
+                    vertical_alignment: alignment::Vertical::Center,
+                    shaping: Shaping::Basic,
+                },
+                badge_bounds.center(),
+                appearance.badge_text_color,
+                *viewport,
+            );
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: layout::Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content.as_widget().operate(
+            &mut tree.children[0],
+            layout.children().next().unwrap(),
+            renderer,
+            operation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<CardTile<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: Stylesheet + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    fn from(card_tile: CardTile<'a, Message, Theme, Renderer>) -> Self {
+        Self::new(card_tile)
+    }
+}
@@ -0,0 +1,39 @@
+use iced_core::{Background, Border, Color};
+
+pub struct Appearance {
+    pub border: Border,
+    /// Drawn over the content, used to dim the cards which are not owned
+    pub overlay: Option<Background>,
+    pub badge_background: Background,
+    pub badge_text_color: Color,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            border: Border {
+                color: Color::BLACK,
+                radius: 5.0.into(),
+                width: 1.0,
+            },
+            overlay: None,
+            badge_background: Background::Color(Color::BLACK),
+            badge_text_color: Color::WHITE,
+        }
+    }
+}
+
+pub trait Stylesheet {
+    type Style: Default;
+
+    fn owned(&self) -> Appearance {
+        Appearance::default()
+    }
+
+    fn missing(&self) -> Appearance {
+        Appearance {
+            overlay: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+            ..self.owned()
+        }
+    }
+}
@@ -1,12 +1,27 @@
-use iced_core::{layout, renderer, Color, Element, Length, Padding, Point, Shadow, Size, Widget};
+use iced_core::{
+    event, layout, mouse, renderer, text,
+    widget::{text::StyleSheet as TextStylesheet, tree, Text, Tree},
+    Alignment, Element, Length, Padding, Point, Rectangle, Shadow, Size, Widget,
+};
 
 use self::style::Stylesheet;
 
 pub mod style;
 
+const SORT_INDICATOR_STEPS: u16 = 5;
+const SORT_INDICATOR_STEP_HEIGHT: f32 = 1.5;
+/// Distance from the border of a column where the cursor can grab it to resize the column
+const RESIZE_HANDLE_WIDTH: f32 = 4.0;
+/// Distance the cursor needs to travel before a pressed column starts to be moved
+const MOVE_THRESHOLD: f32 = 5.0;
+const MIN_COLUMN_WIDTH: f32 = 40.0;
+const DROP_INDICATOR_WIDTH: f32 = 2.0;
+
+#[derive(Debug, Clone)]
 pub struct Column {
     pub name: String,
     pub resizable: bool,
+    pub sortable: bool,
     pub width: Length,
 }
 
@@ -15,6 +30,7 @@ impl Column {
         Self {
             name: name.to_string(),
             resizable: true,
+            sortable: true,
             width: Length::Fixed(150.0),
         }
     }
@@ -23,26 +39,212 @@ impl Column {
         self.width = width;
         self
     }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
 }
 
 pub struct Header<'a, Message, Theme, Renderer> {
-    content: Element<'a, Message, Theme, Renderer>,
+    cells: Vec<Element<'a, Message, Theme, Renderer>>,
+    sortable: Vec<bool>,
+    resizable: Vec<bool>,
+    sort: Option<(usize, SortOrder)>,
+    on_sort: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+    on_resize: Option<Box<dyn Fn(usize, f32) -> Message + 'a>>,
+    on_resize_end: Option<Message>,
+    on_move: Option<Box<dyn Fn(usize, usize) -> Message + 'a>>,
+    height: f32,
+    padding: Padding,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    drag: Option<Drag>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Drag {
+    Resize {
+        index: usize,
+        origin: f32,
+        width: f32,
+    },
+    Press {
+        index: usize,
+        origin: Point,
+        is_moving: bool,
+    },
 }
 
-impl<'a, Message, Theme, Renderer> Header<'a, Message, Theme, Renderer> {
-    pub fn new(content: Element<'a, Message, Theme, Renderer>) -> Self {
-        Self { content }
+impl<'a, Message, Theme, Renderer> Header<'a, Message, Theme, Renderer>
+where
+    Theme: TextStylesheet + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    pub fn new(columns: &[Column]) -> Self {
+        let cells = columns
+            .iter()
+            .map(|column| {
+                Element::new(
+                    Text::new(column.name.clone())
+                        .width(column.width)
+                        .height(Length::Fill)
+                        .vertical_alignment(iced_core::alignment::Vertical::Center),
+                )
+            })
+            .collect();
+
+        Self {
+            cells,
+            sortable: columns.iter().map(|column| column.sortable).collect(),
+            resizable: columns.iter().map(|column| column.resizable).collect(),
+            sort: None,
+            on_sort: None,
+            on_resize: None,
+            on_resize_end: None,
+            on_move: None,
+            height: 30.0,
+            padding: Padding::from([0.0, 10.0]),
+        }
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Padding of the header, it needs to be the same as the one of the rows to keep the cells
+    /// aligned
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Draw the sort indicator on the column at the given index
+    pub fn sort(mut self, sort: Option<(usize, SortOrder)>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Message produced with the index of the column when a sortable column is clicked
+    pub fn on_sort(mut self, on_sort: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_sort = Some(Box::new(on_sort));
+        self
+    }
+
+    /// Message produced with the index of the column and its new width while a column is resized
+    pub fn on_resize(mut self, on_resize: impl Fn(usize, f32) -> Message + 'a) -> Self {
+        self.on_resize = Some(Box::new(on_resize));
+        self
+    }
+
+    /// Message produced when the user releases the border of the resized column
+    pub fn on_resize_end(mut self, message: Message) -> Self {
+        self.on_resize_end = Some(message);
+        self
+    }
+
+    /// Message produced with the previous and the new index of a column dropped on another one
+    pub fn on_move(mut self, on_move: impl Fn(usize, usize) -> Message + 'a) -> Self {
+        self.on_move = Some(Box::new(on_move));
+        self
+    }
+
+    fn sortable_cell_under_cursor(
+        &self,
+        layout: iced_core::Layout<'_>,
+        cursor: mouse::Cursor,
+    ) -> Option<usize> {
+        self.on_sort.as_ref()?;
+
+        self.cell_under_cursor(layout, cursor)
+            .filter(|index| self.sortable[*index])
+    }
+
+    fn cell_under_cursor(
+        &self,
+        layout: iced_core::Layout<'_>,
+        cursor: mouse::Cursor,
+    ) -> Option<usize> {
+        layout
+            .children()
+            .position(|cell_layout| cursor.is_over(cell_layout.bounds()))
+    }
+
+    fn resize_handle_under_cursor(
+        &self,
+        layout: iced_core::Layout<'_>,
+        cursor: mouse::Cursor,
+    ) -> Option<(usize, Rectangle)> {
+        self.on_resize.as_ref()?;
+        let position = cursor.position_over(layout.bounds())?;
+
+        layout
+            .children()
+            .enumerate()
+            .find(|(index, cell_layout)| {
+                let bounds = cell_layout.bounds();
+                self.resizable[*index]
+                    && (position.x - (bounds.x + bounds.width)).abs() <= RESIZE_HANDLE_WIDTH
+            })
+            .map(|(index, cell_layout)| (index, cell_layout.bounds()))
+    }
+
+    /// Index where the moved column would be dropped, the cursor can be outside of the header
+    fn drop_index(&self, layout: iced_core::Layout<'_>, cursor_x: f32) -> usize {
+        let bounds = layout.bounds();
+        let cursor = mouse::Cursor::Available(Point::new(
+            cursor_x.clamp(bounds.x + 1.0, bounds.x + bounds.width - 1.0),
+            bounds.center_y(),
+        ));
+
+        self.cell_under_cursor(layout, cursor)
+            .unwrap_or(if cursor_x < bounds.center_x() {
+                0
+            } else {
+                self.cells.len().saturating_sub(1)
+            })
     }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for Header<'a, Message, Theme, Renderer>
 where
-    Theme: Stylesheet,
-    Renderer: iced_core::Renderer,
+    Theme: Stylesheet + TextStylesheet + 'a,
+    Renderer: text::Renderer + 'a,
+    Message: Clone,
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.cells.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.cells);
+    }
+
     fn size(&self) -> iced_core::Size<iced_core::Length> {
-        Size::new(Length::Fill, Length::Fill)
+        Size::new(Length::Fill, Length::Fixed(self.height))
     }
 
     fn layout(
@@ -51,27 +253,126 @@ where
         renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        let padding: Padding = Padding::from([0.0, 10.0]);
-        let limits = limits
-            .loose()
-            .width(Length::Fill)
-            .height(Length::Fixed(30.0));
-
-        let mut content = self
-            .content
-            .as_widget()
-            .layout(&mut tree.children[0], renderer, &limits);
-        let size = limits.resolve(Length::Fill, Length::Fixed(30.0), content.size());
-
-        content = content
-            .move_to(Point::new(padding.left, padding.top))
-            .align(
-                iced_core::Alignment::Start,
-                iced_core::Alignment::Center,
-                size,
-            );
+        layout::flex::resolve(
+            layout::flex::Axis::Horizontal,
+            renderer,
+            limits,
+            Length::Fill,
+            Length::Fixed(self.height),
+            self.padding,
+            0.0,
+            Alignment::Center,
+            &self.cells,
+            &mut tree.children,
+        )
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: iced_core::Event,
+        layout: iced_core::Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn iced_core::Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        let iced_core::Event::Mouse(mouse_event) = event else {
+            return event::Status::Ignored;
+        };
+
+        match mouse_event {
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                if let Some((index, bounds)) = self.resize_handle_under_cursor(layout, cursor) {
+                    state.drag = Some(Drag::Resize {
+                        index,
+                        origin: cursor.position().map(|position| position.x).unwrap_or(0.0),
+                        width: bounds.width,
+                    });
+                    return event::Status::Captured;
+                }
 
-        layout::Node::with_children(size, vec![content])
+                let Some(index) = self.cell_under_cursor(layout, cursor) else {
+                    return event::Status::Ignored;
+                };
+                if self.on_move.is_none()
+                    && self.sortable_cell_under_cursor(layout, cursor).is_none()
+                {
+                    return event::Status::Ignored;
+                }
+
+                state.drag = Some(Drag::Press {
+                    index,
+                    origin: cursor.position().unwrap_or(Point::ORIGIN),
+                    is_moving: false,
+                });
+                event::Status::Captured
+            }
+            mouse::Event::CursorMoved { position } => match state.drag {
+                Some(Drag::Resize {
+                    index,
+                    origin,
+                    width,
+                }) => {
+                    if let Some(on_resize) = &self.on_resize {
+                        let new_width = (width + position.x - origin).max(MIN_COLUMN_WIDTH);
+                        shell.publish(on_resize(index, new_width));
+                    }
+                    event::Status::Captured
+                }
+                Some(Drag::Press {
+                    index,
+                    origin,
+                    is_moving: false,
+                }) if self.on_move.is_some() && position.distance(origin) > MOVE_THRESHOLD => {
+                    state.drag = Some(Drag::Press {
+                        index,
+                        origin,
+                        is_moving: true,
+                    });
+                    event::Status::Captured
+                }
+                _ => event::Status::Ignored,
+            },
+            mouse::Event::ButtonReleased(mouse::Button::Left) => {
+                let Some(drag) = state.drag.take() else {
+                    return event::Status::Ignored;
+                };
+
+                match drag {
+                    Drag::Resize { .. } => {
+                        if let Some(message) = &self.on_resize_end {
+                            shell.publish(message.clone());
+                        }
+                    }
+                    Drag::Press {
+                        index,
+                        is_moving: true,
+                        ..
+                    } => {
+                        let cursor_x = cursor.position().map(|position| position.x).unwrap_or(0.0);
+                        let drop_index = self.drop_index(layout, cursor_x);
+                        if let (Some(on_move), true) = (&self.on_move, drop_index != index) {
+                            shell.publish(on_move(index, drop_index));
+                        }
+                    }
+                    Drag::Press {
+                        index,
+                        is_moving: false,
+                        ..
+                    } => {
+                        if let (Some(on_sort), true) = (&self.on_sort, self.sortable[index]) {
+                            shell.publish(on_sort(index));
+                        }
+                    }
+                }
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
     }
 
     fn draw(
@@ -81,8 +382,8 @@ where
         theme: &Theme,
         _style: &renderer::Style,
         layout: iced_core::Layout<'_>,
-        cursor: iced_core::mouse::Cursor,
-        viewport: &iced_core::Rectangle,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
     ) {
         let active_theme = theme.active();
         renderer.fill_quad(
@@ -93,31 +394,162 @@ where
             },
             active_theme.background,
         );
-        self.content.as_widget().draw(
-            &tree.children[0],
-            renderer,
-            theme,
-            &renderer::Style {
-                text_color: Color::WHITE,
+
+        for (index, ((cell, state), cell_layout)) in self
+            .cells
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .enumerate()
+        {
+            cell.as_widget().draw(
+                state,
+                renderer,
+                theme,
+                &renderer::Style {
+                    text_color: active_theme.text_color,
+                },
+                cell_layout,
+                cursor,
+                viewport,
+            );
+
+            if let Some((_, order)) = self.sort.filter(|(sorted_index, _)| *sorted_index == index) {
+                draw_sort_indicator(
+                    renderer,
+                    cell_layout.bounds(),
+                    order,
+                    active_theme.sort_indicator,
+                );
+            }
+        }
+
+        let state = tree.state.downcast_ref::<State>();
+        if let (
+            Some(Drag::Press {
+                index,
+                is_moving: true,
+                ..
+            }),
+            Some(position),
+        ) = (state.drag, cursor.position())
+        {
+            let drop_index = self.drop_index(layout, position.x);
+            if let Some(drop_layout) = layout.children().nth(drop_index) {
+                draw_drop_indicator(
+                    renderer,
+                    drop_layout.bounds(),
+                    drop_index > index,
+                    active_theme.drop_indicator,
+                );
+            }
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: iced_core::Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+
+        match state.drag {
+            Some(Drag::Resize { .. }) => mouse::Interaction::ResizingHorizontally,
+            Some(Drag::Press {
+                is_moving: true, ..
+            }) => mouse::Interaction::Grabbing,
+            _ if self.resize_handle_under_cursor(layout, cursor).is_some() => {
+                mouse::Interaction::ResizingHorizontally
+            }
+            _ if self.sortable_cell_under_cursor(layout, cursor).is_some() => {
+                mouse::Interaction::Pointer
+            }
+            _ if self.on_move.is_some() && self.cell_under_cursor(layout, cursor).is_some() => {
+                mouse::Interaction::Grab
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// Draw a small triangle on the right of the cell, pointing up when the order is ascending and
+/// down when it is descending
+fn draw_sort_indicator<Renderer>(
+    renderer: &mut Renderer,
+    cell_bounds: Rectangle,
+    order: SortOrder,
+    color: iced_core::Color,
+) where
+    Renderer: renderer::Renderer,
+{
+    let indicator_height = SORT_INDICATOR_STEPS as f32 * SORT_INDICATOR_STEP_HEIGHT;
+    let max_width = SORT_INDICATOR_STEPS as f32 * 2.0;
+    let center_x = cell_bounds.x + cell_bounds.width - max_width;
+    let top = cell_bounds.center_y() - indicator_height / 2.0;
+
+    for step in 0..SORT_INDICATOR_STEPS {
+        let width = match order {
+            SortOrder::Ascending => (step + 1) as f32 * 2.0,
+            SortOrder::Descending => (SORT_INDICATOR_STEPS - step) as f32 * 2.0,
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: center_x - width / 2.0,
+                    y: top + step as f32 * SORT_INDICATOR_STEP_HEIGHT,
+                    width,
+                    height: SORT_INDICATOR_STEP_HEIGHT,
+                },
+                border: iced_core::Border::default(),
+                shadow: Shadow::default(),
             },
-            layout
-                .children()
-                .next()
-                .expect("The component TableRow need to have content"),
-            cursor,
-            viewport,
+            color,
         );
     }
 }
 
+/// Draw a vertical line on the side of the cell where the moved column will be dropped
+fn draw_drop_indicator<Renderer>(
+    renderer: &mut Renderer,
+    cell_bounds: Rectangle,
+    after_cell: bool,
+    color: iced_core::Color,
+) where
+    Renderer: renderer::Renderer,
+{
+    let x = if after_cell {
+        cell_bounds.x + cell_bounds.width - DROP_INDICATOR_WIDTH
+    } else {
+        cell_bounds.x
+    };
+
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds: Rectangle {
+                x,
+                y: cell_bounds.y,
+                width: DROP_INDICATOR_WIDTH,
+                height: cell_bounds.height,
+            },
+            border: iced_core::Border::default(),
+            shadow: Shadow::default(),
+        },
+        color,
+    );
+}
+
 impl<'a, Message, Theme, Renderer> From<Header<'a, Message, Theme, Renderer>>
     for Element<'a, Message, Theme, Renderer>
 where
-    Message: 'a,
-    Theme: Stylesheet + 'a,
-    Renderer: renderer::Renderer + 'a,
+    Message: Clone + 'a,
+    Theme: Stylesheet + TextStylesheet + 'a,
+    Renderer: text::Renderer + 'a,
 {
-    fn from(row: Header<'a, Message, Theme, Renderer>) -> Self {
-        Self::new(row)
+    fn from(header: Header<'a, Message, Theme, Renderer>) -> Self {
+        Self::new(header)
     }
 }
@@ -3,6 +3,9 @@ use iced_core::{Background, Border, Color};
 pub struct Appearance {
     pub background: Background,
     pub border: Border,
+    pub text_color: Color,
+    pub sort_indicator: Color,
+    pub drop_indicator: Color,
 }
 
 impl Default for Appearance {
@@ -14,6 +17,9 @@ impl Default for Appearance {
                 radius: 5.0.into(),
                 width: 1.0,
             },
+            text_color: Color::BLACK,
+            sort_indicator: Color::BLACK,
+            drop_indicator: Color::BLACK,
         }
     }
 }
@@ -1,2 +1,5 @@
+pub mod card_tile;
 pub mod header;
+pub mod stepper;
 pub mod table_row;
+pub mod virtual_list;
@@ -0,0 +1,599 @@
+use iced_core::{
+    alignment, event,
+    keyboard::{self, key::Named, Key},
+    layout, mouse, renderer,
+    text::{self, LineHeight, Paragraph, Shaping},
+    widget::{
+        operation::{self, focusable::Focusable, Operation},
+        tree, Tree,
+    },
+    Element, Length, Pixels, Point, Rectangle, Shadow, Size, Widget,
+};
+
+use self::style::Stylesheet;
+
+pub mod style;
+
+pub use iced_core::widget::Id;
+
+const CARET_WIDTH: f32 = 1.0;
+
+/// Numeric input holding a value between a minimum and a maximum.
+///
+/// The value can be typed, changed with the buttons on each side, the `+` and `-` keys or the mouse
+/// wheel when the stepper is focused or the command key is held. A typed value is only published
+/// when enter is pressed or when the stepper loses the focus, and only if it is valid, otherwise
+/// the stepper is drawn with the invalid style and goes back to its value.
+pub struct Stepper<'a, Message> {
+    id: Option<Id>,
+    value: u8,
+    min: u8,
+    max: u8,
+    on_change: Box<dyn Fn(u8) -> Message + 'a>,
+    on_submit: Option<Message>,
+    on_focus: Option<Message>,
+    on_blur: Option<Message>,
+    width: Length,
+    height: f32,
+    text_size: Option<Pixels>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    is_focused: bool,
+    /// Text typed by the user while it does not match the value of the stepper
+    buffer: Option<String>,
+    /// The whole value is selected, so the next typed digit replaces it
+    is_selected: bool,
+    /// Focus at the end of the previous event, to publish the focus changes made by the operations
+    was_focused: bool,
+    modifiers: keyboard::Modifiers,
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+        self.is_selected = true;
+        self.buffer = None;
+    }
+
+    fn unfocus(&mut self) {
+        // The typed text is published or discarded with the next event
+        self.is_focused = false;
+        self.is_selected = false;
+    }
+}
+
+/// Parts of the stepper the cursor can interact with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Decrement,
+    Value,
+    Increment,
+}
+
+impl<'a, Message> Stepper<'a, Message>
+where
+    Message: Clone,
+{
+    pub fn new(value: u8, on_change: impl Fn(u8) -> Message + 'a) -> Self {
+        Self {
+            id: None,
+            value,
+            min: u8::MIN,
+            max: u8::MAX,
+            on_change: Box::new(on_change),
+            on_submit: None,
+            on_focus: None,
+            on_blur: None,
+            width: Length::Fill,
+            height: 30.0,
+            text_size: None,
+        }
+    }
+
+    /// Id used to focus the stepper with the focus commands
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn min(mut self, min: u8) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: u8) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Message published when the enter key is pressed with a valid value
+    pub fn on_submit(mut self, message: Message) -> Self {
+        self.on_submit = Some(message);
+        self
+    }
+
+    /// Message published when the stepper gains the focus
+    pub fn on_focus(mut self, message: Message) -> Self {
+        self.on_focus = Some(message);
+        self
+    }
+
+    /// Message published when the stepper loses the focus, after the typed value
+    pub fn on_blur(mut self, message: Message) -> Self {
+        self.on_blur = Some(message);
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = Some(size.into());
+        self
+    }
+
+    fn parse(&self, text: &str) -> Option<u8> {
+        text.parse::<u8>()
+            .ok()
+            .filter(|value| (self.min..=self.max).contains(value))
+    }
+
+    fn step(&self, state: &mut State, shell: &mut iced_core::Shell<'_, Message>, up: bool) {
+        let value = if up {
+            self.value.saturating_add(1).min(self.max)
+        } else {
+            self.value.saturating_sub(1).max(self.min)
+        };
+
+        state.buffer = None;
+        if value != self.value {
+            shell.publish((self.on_change)(value));
+        }
+    }
+
+    /// Replace the typed text, it is published by [`Self::commit`]
+    fn edit(&self, state: &mut State, text: String) {
+        state.is_selected = false;
+        state.buffer = Some(text);
+    }
+
+    /// Publish the typed text if it is a valid value, returns false when it is invalid
+    fn commit(&self, state: &mut State, shell: &mut iced_core::Shell<'_, Message>) -> bool {
+        let Some(buffer) = state.buffer.take() else {
+            return true;
+        };
+
+        match self.parse(&buffer) {
+            Some(value) => {
+                if value != self.value {
+                    shell.publish((self.on_change)(value));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Publish the focus changes, including the ones made by the focus operations
+    fn notify_focus_change(&self, state: &mut State, shell: &mut iced_core::Shell<'_, Message>) {
+        if state.is_focused == state.was_focused {
+            return;
+        }
+        state.was_focused = state.is_focused;
+
+        if state.is_focused {
+            if let Some(on_focus) = self.on_focus.clone() {
+                shell.publish(on_focus);
+            }
+        } else {
+            self.commit(state, shell);
+            if let Some(on_blur) = self.on_blur.clone() {
+                shell.publish(on_blur);
+            }
+        }
+    }
+
+    fn is_invalid(&self, state: &State) -> bool {
+        state
+            .buffer
+            .as_ref()
+            .is_some_and(|buffer| self.parse(buffer).is_none())
+    }
+
+    fn part_at(&self, bounds: Rectangle, position: Point) -> Option<Part> {
+        if !bounds.contains(position) {
+            return None;
+        }
+
+        let button_width = bounds.height;
+        Some(if position.x < bounds.x + button_width {
+            Part::Decrement
+        } else if position.x > bounds.x + bounds.width - button_width {
+            Part::Increment
+        } else {
+            Part::Value
+        })
+    }
+
+    fn handle_event(
+        &self,
+        state: &mut State,
+        event: iced_core::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        shell: &mut iced_core::Shell<'_, Message>,
+    ) -> event::Status {
+        match event {
+            iced_core::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let part = cursor
+                    .position()
+                    .and_then(|position| self.part_at(bounds, position));
+
+                match part {
+                    Some(Part::Decrement) => self.step(state, shell, false),
+                    Some(Part::Increment) => self.step(state, shell, true),
+                    Some(Part::Value) => {}
+                    None => {
+                        state.unfocus();
+                        return event::Status::Ignored;
+                    }
+                }
+                // The buttons focus the stepper too, so the changes are published on blur
+                if !state.is_focused {
+                    state.focus();
+                }
+
+                event::Status::Captured
+            }
+            iced_core::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+                event::Status::Ignored
+            }
+            // The wheel scrolls the lists unless the stepper is focused or the command key is held
+            iced_core::Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if cursor.is_over(bounds) && (state.is_focused || state.modifiers.command()) =>
+            {
+                let y = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => y,
+                };
+                if y == 0.0 {
+                    return event::Status::Ignored;
+                }
+                self.step(state, shell, y > 0.0);
+
+                event::Status::Captured
+            }
+            iced_core::Event::Keyboard(keyboard::Event::KeyPressed {
+                key,
+                modifiers,
+                text,
+                ..
+            }) if state.is_focused => {
+                if modifiers.command() || modifiers.alt() {
+                    return event::Status::Ignored;
+                }
+
+                match key.as_ref() {
+                    Key::Named(Named::Enter) => {
+                        let is_valid = self.commit(state, shell);
+                        state.is_selected = true;
+
+                        if let (true, Some(on_submit)) = (is_valid, self.on_submit.clone()) {
+                            shell.publish(on_submit);
+                        }
+                    }
+                    Key::Named(Named::Escape) => {
+                        state.buffer = None;
+                        state.is_selected = true;
+                    }
+                    Key::Named(Named::Backspace | Named::Delete) => {
+                        let mut buffer = if state.is_selected {
+                            String::new()
+                        } else {
+                            state
+                                .buffer
+                                .clone()
+                                .unwrap_or_else(|| self.value.to_string())
+                        };
+                        buffer.pop();
+                        self.edit(state, buffer);
+                    }
+                    _ => match text.as_deref() {
+                        Some("+") => self.step(state, shell, true),
+                        Some("-") => self.step(state, shell, false),
+                        Some(digit) if digit.chars().all(|c| c.is_ascii_digit()) => {
+                            let mut buffer = if state.is_selected {
+                                String::new()
+                            } else {
+                                state
+                                    .buffer
+                                    .clone()
+                                    .unwrap_or_else(|| self.value.to_string())
+                            };
+                            buffer.push_str(digit);
+                            self.edit(state, buffer);
+                        }
+                        // Let the application use the other keys, like tab, to navigate
+                        _ => return event::Status::Ignored,
+                    },
+                }
+
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Stepper<'a, Message>
+where
+    Message: Clone,
+    Theme: Stylesheet,
+    Renderer: text::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, Length::Fixed(self.height))
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::atomic(limits, self.width, Length::Fixed(self.height))
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: layout::Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        operation.focusable(state, self.id.as_ref());
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: iced_core::Event,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn iced_core::Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let status = self.handle_event(state, event, layout.bounds(), cursor, shell);
+        self.notify_focus_change(state, shell);
+
+        status
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let hovered_part = cursor
+            .position()
+            .and_then(|position| self.part_at(bounds, position));
+
+        let appearance = if self.is_invalid(state) {
+            theme.invalid()
+        } else if state.is_focused {
+            theme.focused()
+        } else if hovered_part.is_some() {
+            theme.hovered()
+        } else {
+            theme.active()
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: appearance.border,
+                shadow: Shadow::default(),
+            },
+            appearance.background,
+        );
+
+        let text_size = self.text_size.unwrap_or_else(|| renderer.default_size());
+        let button_width = bounds.height;
+        let buttons = [
+            (Part::Decrement, "-", bounds.x, self.value > self.min),
+            (
+                Part::Increment,
+                "+",
+                bounds.x + bounds.width - button_width,
+                self.value < self.max,
+            ),
+        ];
+        for (part, label, x, is_enabled) in buttons {
+            let button_bounds = Rectangle {
+                x,
+                y: bounds.y,
+                width: button_width,
+                height: bounds.height,
+            };
+            let background = if is_enabled && hovered_part == Some(part) {
+                appearance.hovered_button_background
+            } else {
+                appearance.button_background
+            };
+            let text_color = if is_enabled {
+                appearance.button_text_color
+            } else {
+                appearance.disabled_button_text_color
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: button_bounds,
+                    border: appearance.border,
+                    shadow: Shadow::default(),
+                },
+                background,
+            );
+            renderer.fill_text(
+                centered_text(
+                    label,
+                    button_bounds.size(),
+                    text_size,
+                    renderer.default_font(),
+                ),
+                button_bounds.center(),
+                text_color,
+                *viewport,
+            );
+        }
+
+        let value = state
+            .buffer
+            .clone()
+            .unwrap_or_else(|| self.value.to_string());
+        let value_bounds = Rectangle {
+            x: bounds.x + button_width,
+            y: bounds.y,
+            width: (bounds.width - 2.0 * button_width).max(0.0),
+            height: bounds.height,
+        };
+        let value_text = centered_text(
+            &value,
+            value_bounds.size(),
+            text_size,
+            renderer.default_font(),
+        );
+
+        if state.is_focused {
+            let text_width = Renderer::Paragraph::with_text(value_text).min_width();
+            let text_height = LineHeight::default().to_absolute(text_size).0;
+            let center = value_bounds.center();
+
+            let (bounds, color) = if state.is_selected {
+                (
+                    Rectangle {
+                        x: center.x - text_width / 2.0,
+                        y: center.y - text_height / 2.0,
+                        width: text_width,
+                        height: text_height,
+                    },
+                    appearance.selection,
+                )
+            } else {
+                (
+                    Rectangle {
+                        x: center.x + text_width / 2.0,
+                        y: center.y - text_height / 2.0,
+                        width: CARET_WIDTH,
+                        height: text_height,
+                    },
+                    appearance.text_color,
+                )
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds,
+                    border: Default::default(),
+                    shadow: Shadow::default(),
+                },
+                color,
+            );
+        }
+
+        renderer.fill_text(
+            value_text,
+            value_bounds.center(),
+            appearance.text_color,
+            value_bounds,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let part = cursor
+            .position()
+            .and_then(|position| self.part_at(layout.bounds(), position));
+
+        match part {
+            Some(Part::Decrement) if self.value > self.min => mouse::Interaction::Pointer,
+            Some(Part::Increment) if self.value < self.max => mouse::Interaction::Pointer,
+            Some(Part::Value) => mouse::Interaction::Text,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+fn centered_text<Font>(
+    content: &str,
+    bounds: Size,
+    size: Pixels,
+    font: Font,
+) -> text::Text<'_, Font> {
+    text::Text {
+        content,
+        bounds,
+        size,
+        line_height: LineHeight::default(),
+        font,
+        horizontal_alignment: alignment::Horizontal::Center,
+        vertical_alignment: alignment::Vertical::Center,
+        shaping: Shaping::Basic,
+    }
+}
+
+/// Produces an [`Operation`] focusing the [`Stepper`] with the given [`Id`], its value is
+/// selected so typing a number replaces it
+pub fn focus<T>(target: Id) -> impl Operation<T> {
+    operation::focusable::focus(target)
+}
+
+impl<'a, Message, Theme, Renderer> From<Stepper<'a, Message>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: Clone + 'a,
+    Theme: Stylesheet + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    fn from(stepper: Stepper<'a, Message>) -> Self {
+        Self::new(stepper)
+    }
+}
@@ -0,0 +1,58 @@
+use iced_core::{Background, Border, Color};
+
+pub struct Appearance {
+    pub background: Background,
+    pub border: Border,
+    pub text_color: Color,
+    pub selection: Color,
+    pub button_background: Background,
+    pub hovered_button_background: Background,
+    pub button_text_color: Color,
+    pub disabled_button_text_color: Color,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            background: Background::Color(Color::WHITE),
+            border: Border {
+                color: Color::BLACK,
+                radius: 5.0.into(),
+                width: 1.0,
+            },
+            text_color: Color::BLACK,
+            selection: Color::from_rgb(0.8, 0.8, 1.0),
+            button_background: Background::Color(Color::TRANSPARENT),
+            hovered_button_background: Background::Color(Color::from_rgb(0.9, 0.9, 0.9)),
+            button_text_color: Color::BLACK,
+            disabled_button_text_color: Color::from_rgb(0.6, 0.6, 0.6),
+        }
+    }
+}
+
+pub trait Stylesheet {
+    type Style: Default;
+
+    fn active(&self) -> Appearance {
+        Appearance::default()
+    }
+
+    fn hovered(&self) -> Appearance {
+        self.active()
+    }
+
+    fn focused(&self) -> Appearance {
+        self.active()
+    }
+
+    /// Appearance of the stepper while the typed value can not be saved
+    fn invalid(&self) -> Appearance {
+        Appearance {
+            border: Border {
+                color: Color::from_rgb(0.8, 0.0, 0.0),
+                ..self.focused().border
+            },
+            ..self.focused()
+        }
+    }
+}
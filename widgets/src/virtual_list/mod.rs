@@ -0,0 +1,548 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use iced_core::{
+    event, layout, mouse, renderer,
+    widget::{
+        operation::{
+            self,
+            scrollable::{AbsoluteOffset, RelativeOffset},
+        },
+        tree, Id, Operation, Tree,
+    },
+    Element, Length, Point, Rectangle, Shadow, Size, Vector, Widget,
+};
+
+use self::style::Stylesheet;
+
+pub mod style;
+
+/// Width of the scrollbar drawn on the right of the list
+pub const SCROLLBAR_WIDTH: f32 = 10.0;
+/// Space between the rows and the scrollbar
+pub const SCROLLBAR_MARGIN: f32 = 5.0;
+const MIN_SCROLLER_HEIGHT: f32 = 20.0;
+/// Number of pixels scrolled for each line reported by the mouse wheel
+const PIXELS_PER_LINE: f32 = 60.0;
+
+type ViewRow<'a, Message, Theme, Renderer> =
+    Box<dyn Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a>;
+type RowKey<'a> = Box<dyn Fn(usize) -> u64 + 'a>;
+
+/// A scrollable list of rows of the same height where only the visible rows are created, laid
+/// out and drawn, allowing to display thousands of rows without slowing down the application.
+pub struct VirtualList<'a, Message, Theme, Renderer> {
+    id: Option<Id>,
+    row_count: usize,
+    row_height: f32,
+    spacing: f32,
+    width: Length,
+    height: Length,
+    view_row: ViewRow<'a, Message, Theme, Renderer>,
+    row_key: RowKey<'a>,
+    rows: RefCell<Vec<Element<'a, Message, Theme, Renderer>>>,
+}
+
+impl<'a, Message, Theme, Renderer> VirtualList<'a, Message, Theme, Renderer> {
+    /// Create a list of `row_count` rows, the `view_row` closure is called with the index of
+    /// each row when it becomes visible
+    pub fn new(
+        row_count: usize,
+        view_row: impl Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a,
+    ) -> Self {
+        Self {
+            id: None,
+            row_count,
+            row_height: 35.0,
+            spacing: 0.0,
+            width: Length::Fill,
+            height: Length::Fill,
+            view_row: Box::new(view_row),
+            row_key: Box::new(|index| index as u64),
+            rows: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Id used to scroll the list with the scrollable commands
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Key of the content of each row, like the id of the displayed card. The state of a row,
+    /// like a focused input, follows its key when the rows are sorted or filtered, without a key
+    /// it stays at the position of the row
+    pub fn row_key<K: Hash>(mut self, row_key: impl Fn(usize) -> K + 'a) -> Self {
+        self.row_key = Box::new(move |index| {
+            let mut hasher = DefaultHasher::new();
+            row_key(index).hash(&mut hasher);
+            hasher.finish()
+        });
+        self
+    }
+
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    fn stride(&self) -> f32 {
+        self.row_height + self.spacing
+    }
+
+    fn content_height(&self) -> f32 {
+        (self.row_count as f32 * self.stride() - self.spacing).max(0.0)
+    }
+
+    fn max_offset(&self, viewport_height: f32) -> f32 {
+        (self.content_height() - viewport_height).max(0.0)
+    }
+
+    fn scroller_bounds(&self, bounds: Rectangle, offset: f32) -> Option<Rectangle> {
+        let max_offset = self.max_offset(bounds.height);
+        if max_offset <= 0.0 {
+            return None;
+        }
+
+        let height = (bounds.height * bounds.height / self.content_height())
+            .clamp(MIN_SCROLLER_HEIGHT, bounds.height);
+        Some(Rectangle {
+            x: bounds.x + bounds.width - SCROLLBAR_WIDTH,
+            y: bounds.y + offset / max_offset * (bounds.height - height),
+            width: SCROLLBAR_WIDTH,
+            height,
+        })
+    }
+
+    /// Offset of the list when the top of the scroller is moved at the given position
+    fn offset_from_scroller(&self, bounds: Rectangle, scroller_height: f32, top: f32) -> f32 {
+        let track_height = (bounds.height - scroller_height).max(1.0);
+        let ratio = ((top - bounds.y) / track_height).clamp(0.0, 1.0);
+        ratio * self.max_offset(bounds.height)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    offset: f32,
+    pending_snap: Option<f32>,
+    pending_row: Option<usize>,
+    scroller_grabbed_at: Option<f32>,
+    rows: Vec<RowState>,
+}
+
+/// State of a visible row
+#[derive(Debug)]
+struct RowState {
+    index: usize,
+    key: u64,
+    tree: Tree,
+}
+
+impl operation::Scrollable for State {
+    fn snap_to(&mut self, offset: RelativeOffset) {
+        // The height of the list is only known during the next layout
+        self.pending_snap = Some(offset.y.clamp(0.0, 1.0));
+    }
+
+    fn scroll_to(&mut self, offset: AbsoluteOffset) {
+        self.offset = offset.y.max(0.0);
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for VirtualList<'a, Message, Theme, Renderer>
+where
+    Theme: Stylesheet,
+    Renderer: iced_core::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let state = tree.state.downcast_mut::<State>();
+        let size = limits.resolve(self.width, self.height, Size::ZERO);
+
+        let max_offset = self.max_offset(size.height);
+        if let Some(relative_offset) = state.pending_snap.take() {
+            state.offset = relative_offset * max_offset;
+        }
+        if let Some(row) = state.pending_row.take() {
+            // Scroll as little as possible to display the whole row
+            let row_top = row as f32 * self.stride();
+            let row_bottom = row_top + self.row_height;
+            if row_top < state.offset {
+                state.offset = row_top;
+            } else if row_bottom > state.offset + size.height {
+                state.offset = row_bottom - size.height;
+            }
+        }
+        state.offset = state.offset.clamp(0.0, max_offset);
+
+        // Only create the rows intersecting with the viewport
+        let first_row = (state.offset / self.stride()).floor() as usize;
+        let last_row =
+            (((state.offset + size.height) / self.stride()).ceil() as usize).min(self.row_count);
+        let mut rows = self.rows.borrow_mut();
+        *rows = (first_row..last_row)
+            .map(|index| (self.view_row)(index))
+            .collect();
+
+        // Keep the state of the rows that were already visible
+        let mut previous_rows = std::mem::take(&mut state.rows);
+        state.rows = rows
+            .iter()
+            .zip(first_row..last_row)
+            .map(|(row, index)| {
+                let key = (self.row_key)(index);
+                let tree = match previous_rows
+                    .iter()
+                    .position(|previous_row| previous_row.key == key)
+                {
+                    Some(position) => {
+                        let mut tree = previous_rows.swap_remove(position).tree;
+                        tree.diff(row);
+                        tree
+                    }
+                    None => Tree::new(row),
+                };
+                RowState { index, key, tree }
+            })
+            .collect();
+
+        let row_limits = layout::Limits::new(
+            Size::ZERO,
+            Size::new(
+                (size.width - SCROLLBAR_WIDTH - SCROLLBAR_MARGIN).max(0.0),
+                self.row_height,
+            ),
+        );
+        let children = rows
+            .iter()
+            .zip(state.rows.iter_mut())
+            .map(|(row, RowState { index, tree, .. })| {
+                row.as_widget()
+                    .layout(tree, renderer, &row_limits)
+                    .move_to(Point::new(
+                        0.0,
+                        *index as f32 * self.stride() - state.offset,
+                    ))
+            })
+            .collect();
+
+        layout::Node::with_children(size, children)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: iced_core::Event,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn iced_core::Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+        let scroller = self.scroller_bounds(bounds, state.offset);
+
+        if let iced_core::Event::Mouse(mouse_event) = event {
+            match mouse_event {
+                mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                    if let (Some(scroller), Some(position)) =
+                        (scroller, cursor.position_over(bounds))
+                    {
+                        if position.x >= scroller.x {
+                            // Clicking on the track moves the center of the scroller to the cursor
+                            let grabbed_at = if scroller.contains(position) {
+                                position.y - scroller.y
+                            } else {
+                                scroller.height / 2.0
+                            };
+                            state.scroller_grabbed_at = Some(grabbed_at);
+                            state.offset = self.offset_from_scroller(
+                                bounds,
+                                scroller.height,
+                                position.y - grabbed_at,
+                            );
+                            shell.invalidate_layout();
+                            return event::Status::Captured;
+                        }
+                    }
+                }
+                mouse::Event::CursorMoved { position } => {
+                    if let (Some(grabbed_at), Some(scroller)) =
+                        (state.scroller_grabbed_at, scroller)
+                    {
+                        state.offset = self.offset_from_scroller(
+                            bounds,
+                            scroller.height,
+                            position.y - grabbed_at,
+                        );
+                        shell.invalidate_layout();
+                        return event::Status::Captured;
+                    }
+                }
+                mouse::Event::ButtonReleased(mouse::Button::Left)
+                    if state.scroller_grabbed_at.is_some() =>
+                {
+                    state.scroller_grabbed_at = None;
+                    return event::Status::Captured;
+                }
+                _ => {}
+            }
+        }
+
+        let rows_cursor = visible_cursor(cursor, bounds);
+        let status = self
+            .rows
+            .borrow_mut()
+            .iter_mut()
+            .zip(state.rows.iter_mut())
+            .zip(layout.children())
+            .map(|((row, RowState { tree, .. }), row_layout)| {
+                row.as_widget_mut().on_event(
+                    tree,
+                    event.clone(),
+                    row_layout,
+                    rows_cursor,
+                    renderer,
+                    clipboard,
+                    shell,
+                    &bounds,
+                )
+            })
+            .fold(event::Status::Ignored, event::Status::merge);
+
+        // The rows can use the wheel first, like a focused stepper
+        match event {
+            iced_core::Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if status == event::Status::Ignored && cursor.is_over(bounds) =>
+            {
+                let delta_y = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y * PIXELS_PER_LINE,
+                    mouse::ScrollDelta::Pixels { y, .. } => y,
+                };
+                state.offset = (state.offset - delta_y).clamp(0.0, self.max_offset(bounds.height));
+                shell.invalidate_layout();
+                event::Status::Captured
+            }
+            _ => status,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let rows_cursor = visible_cursor(cursor, bounds);
+
+        renderer.with_layer(bounds, |renderer| {
+            for ((row, RowState { tree, .. }), row_layout) in self
+                .rows
+                .borrow()
+                .iter()
+                .zip(state.rows.iter())
+                .zip(layout.children())
+            {
+                row.as_widget().draw(
+                    tree,
+                    renderer,
+                    theme,
+                    style,
+                    row_layout,
+                    rows_cursor,
+                    &bounds,
+                );
+            }
+        });
+
+        let Some(scroller) = self.scroller_bounds(bounds, state.offset) else {
+            return;
+        };
+        let is_hovered = state.scroller_grabbed_at.is_some()
+            || cursor
+                .position_over(bounds)
+                .is_some_and(|position| position.x >= scroller.x);
+        let appearance = if is_hovered {
+            theme.hovered()
+        } else {
+            theme.active()
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    y: bounds.y,
+                    height: bounds.height,
+                    ..scroller
+                },
+                border: iced_core::Border {
+                    radius: appearance.radius.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+            },
+            appearance.scrollbar,
+        );
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: scroller,
+                border: iced_core::Border {
+                    radius: appearance.radius.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+            },
+            appearance.scroller,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: layout::Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let rows_cursor = visible_cursor(cursor, bounds);
+
+        self.rows
+            .borrow()
+            .iter()
+            .zip(state.rows.iter())
+            .zip(layout.children())
+            .map(|((row, RowState { tree, .. }), row_layout)| {
+                row.as_widget()
+                    .mouse_interaction(tree, row_layout, rows_cursor, &bounds, renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: layout::Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        operation.scrollable(state, self.id.as_ref(), bounds, Vector::ZERO);
+        operation.custom(state, self.id.as_ref());
+
+        operation.container(self.id.as_ref(), bounds, &mut |operation| {
+            for ((row, RowState { tree, .. }), row_layout) in self
+                .rows
+                .borrow()
+                .iter()
+                .zip(state.rows.iter_mut())
+                .zip(layout.children())
+            {
+                row.as_widget()
+                    .operate(tree, row_layout, renderer, operation);
+            }
+        });
+    }
+}
+
+/// Produces an [`Operation`] scrolling the [`VirtualList`] with the given [`Id`] until the row
+/// at the given index is visible
+pub fn scroll_into_view<T>(target: Id, row: usize) -> impl Operation<T> {
+    struct ScrollIntoView {
+        target: Id,
+        row: usize,
+    }
+
+    impl<T> Operation<T> for ScrollIntoView {
+        fn container(
+            &mut self,
+            _id: Option<&Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(&mut self, state: &mut dyn Any, id: Option<&Id>) {
+            if Some(&self.target) != id {
+                return;
+            }
+
+            if let Some(state) = state.downcast_mut::<State>() {
+                state.pending_row = Some(self.row);
+            }
+        }
+    }
+
+    ScrollIntoView { target, row }
+}
+
+/// The rows partially hidden by the viewport must not react to the cursor on their hidden part
+fn visible_cursor(cursor: mouse::Cursor, bounds: Rectangle) -> mouse::Cursor {
+    if cursor.is_over(bounds) {
+        cursor
+    } else {
+        mouse::Cursor::Unavailable
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<VirtualList<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: Stylesheet + 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(list: VirtualList<'a, Message, Theme, Renderer>) -> Self {
+        Self::new(list)
+    }
+}
@@ -0,0 +1,29 @@
+use iced_core::{Background, Color};
+
+pub struct Appearance {
+    pub scrollbar: Background,
+    pub scroller: Background,
+    pub radius: f32,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            scrollbar: Background::Color(Color::TRANSPARENT),
+            scroller: Background::Color(Color::BLACK),
+            radius: 5.0,
+        }
+    }
+}
+
+pub trait Stylesheet {
+    type Style: Default;
+
+    fn active(&self) -> Appearance {
+        Appearance::default()
+    }
+
+    fn hovered(&self) -> Appearance {
+        self.active()
+    }
+}